@@ -0,0 +1,43 @@
+//! Generates Rust bindings for every contract ABI in `abi/`
+//!
+//! Each `abi/{contract}.json` becomes `abi::{contract}` with its `functions`
+//! and `events` submodules, so adding a contract only requires dropping its
+//! ABI JSON into the `abi/` directory.
+
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+use substreams_ethereum::Abigen;
+
+fn main() -> Result<()> {
+    let abi_dir = Path::new("abi");
+    let out_dir = PathBuf::from(std::env::var("OUT_DIR")?).join("abi");
+    fs::create_dir_all(&out_dir)?;
+
+    println!("cargo:rerun-if-changed={}", abi_dir.display());
+
+    let mut contracts: Vec<String> = fs::read_dir(abi_dir)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .filter_map(|path| path.file_stem()?.to_str().map(str::to_string))
+        .collect();
+    contracts.sort();
+
+    let mut modules = String::new();
+    for contract in &contracts {
+        let abi_path = abi_dir.join(format!("{}.json", contract));
+        println!("cargo:rerun-if-changed={}", abi_path.display());
+
+        Abigen::new(contract, &abi_path.to_string_lossy().to_string())
+            .and_then(|abigen| abigen.generate())
+            .and_then(|bindings| bindings.write_to_file(out_dir.join(format!("{}.rs", contract))))
+            .with_context(|| format!("generating bindings for {}", abi_path.display()))?;
+
+        modules.push_str(&format!(
+            "#[allow(dead_code, clippy::all)]\npub mod {contract} {{\n    include!(concat!(env!(\"OUT_DIR\"), \"/abi/{contract}.rs\"));\n}}\n",
+        ));
+    }
+
+    fs::write(out_dir.join("mod.rs"), modules)?;
+    Ok(())
+}
@@ -1,5 +1,5 @@
 -- Aerodrome Finance Substreams ClickHouse Schema
--- Optimized for high-performance analytics with materialized views
+-- Optimized for high-performance analytics
 
 -- ====================
 -- Core Tables
@@ -15,31 +15,409 @@ CREATE TABLE IF NOT EXISTS aerodrome_swaps (
     pool_address String,
     sender String,
     recipient String,
-    amount0_in String,
-    amount1_in String,
-    amount0_out String,
-    amount1_out String
+    amount0_in UInt256,
+    amount1_in UInt256,
+    amount0_out UInt256,
+    amount1_out UInt256,
+    token0 String,
+    token1 String,
+    amount0_in_decimal Decimal256(38),
+    amount1_in_decimal Decimal256(38),
+    amount0_out_decimal Decimal256(38),
+    amount1_out_decimal Decimal256(38),
+    token_in String,
+    token_out String,
+    amount_in UInt256,
+    amount_out UInt256,
+    amount_in_decimal Decimal256(38),
+    amount_out_decimal Decimal256(38),
+    two_sided Bool,
+    -- Decimal-adjusted prices, NULL when undefined
+    price0 Nullable(Decimal256(38)),
+    price1 Nullable(Decimal256(38)),
+    mid_price0 Nullable(Decimal256(38)),
+    mid_price1 Nullable(Decimal256(38)),
+    price_impact Nullable(Decimal256(38)),
+    amount_usd Nullable(Decimal256(38)), -- NULL when neither token is priced
+    fee_rate UInt64, -- basis points
+    -- LP fees charged on the input side(s)
+    fee0 UInt256,
+    fee1 UInt256,
+    fee0_decimal Decimal256(38),
+    fee1_decimal Decimal256(38)
 )
 ENGINE = MergeTree()
 ORDER BY (pool_address, timestamp, tx_hash)
 PARTITION BY toYYYYMM(toDateTime(timestamp));
 
+-- Slipstream (concentrated liquidity) swap events
+CREATE TABLE IF NOT EXISTS aerodrome_cl_swaps (
+    id String,
+    tx_hash String,
+    log_index UInt64,
+    block_number UInt64,
+    timestamp Int64,
+    pool_address String,
+    tick_spacing Int32,
+    sender String,
+    recipient String,
+    token0 String,
+    token1 String,
+    -- Signed pool balance deltas, positive when paid into the pool
+    amount0 Int256,
+    amount1 Int256,
+    amount0_decimal Decimal256(38),
+    amount1_decimal Decimal256(38),
+    token_in String,
+    token_out String,
+    amount_in UInt256,
+    amount_out UInt256,
+    amount_in_decimal Decimal256(38),
+    amount_out_decimal Decimal256(38),
+    sqrt_price_x96 UInt256,
+    liquidity UInt256,
+    tick Int32,
+    price0 Nullable(Decimal256(38)),
+    price1 Nullable(Decimal256(38)),
+    pool_price0 Nullable(Decimal256(38))
+)
+ENGINE = MergeTree()
+ORDER BY (pool_address, timestamp, tx_hash)
+PARTITION BY toYYYYMM(toDateTime(timestamp));
+
+-- Liquidity events (Mint/Burn)
+CREATE TABLE IF NOT EXISTS aerodrome_liquidity_events (
+    id String,
+    tx_hash String,
+    log_index UInt64,
+    block_number UInt64,
+    timestamp Int64,
+    pool_address String,
+    action String,
+    sender String,
+    recipient String,
+    token0 String,
+    token1 String,
+    amount0 UInt256,
+    amount1 UInt256,
+    amount0_decimal Decimal256(38),
+    amount1_decimal Decimal256(38),
+    -- Slipstream pools only
+    tick_lower Nullable(Int32),
+    tick_upper Nullable(Int32),
+    liquidity Nullable(UInt256)
+)
+ENGINE = MergeTree()
+ORDER BY (pool_address, timestamp, tx_hash)
+PARTITION BY toYYYYMM(toDateTime(timestamp));
+
+-- Slipstream LP positions (NonfungiblePositionManager NFTs)
+CREATE TABLE IF NOT EXISTS cl_positions (
+    token_id String,
+    pool_address String,
+    token0 String,
+    token1 String,
+    tick_lower Int32,
+    tick_upper Int32,
+    owner String,
+    liquidity UInt256,
+    collected0 UInt256, -- principal and fees collected
+    collected1 UInt256,
+    withdrawn0 UInt256, -- principal released by DecreaseLiquidity
+    withdrawn1 UInt256
+)
+ENGINE = ReplacingMergeTree()
+ORDER BY token_id;
+
+-- Slipstream liquidityNet per initialized tick
+CREATE TABLE IF NOT EXISTS cl_ticks (
+    pool_address String,
+    tick Int32,
+    liquidity_net Int256,
+    block_number UInt64
+)
+ENGINE = ReplacingMergeTree(block_number)
+ORDER BY (pool_address, tick);
+
+-- Slipstream pool active liquidity and price
+CREATE TABLE IF NOT EXISTS cl_pool_state (
+    pool_address String,
+    liquidity UInt256,
+    sqrt_price_x96 UInt256,
+    tick Int32,
+    block_number UInt64,
+    timestamp UInt64
+)
+ENGINE = ReplacingMergeTree(block_number)
+ORDER BY pool_address;
+
+-- veAERO lock events (VotingEscrow)
+CREATE TABLE IF NOT EXISTS ve_lock_events (
+    id String,
+    tx_hash String,
+    log_index UInt64,
+    block_number UInt64,
+    timestamp UInt64,
+    token_id UInt256,
+    action String,
+    owner String,
+    amount Int256,
+    unlock_time UInt64,
+    deposit_type String,
+    related_token_id String
+)
+ENGINE = MergeTree()
+ORDER BY (token_id, block_number, log_index)
+PARTITION BY toYYYYMM(toDateTime(timestamp));
+
+-- Current veAERO locks by NFT token id
+CREATE TABLE IF NOT EXISTS ve_locks (
+    token_id UInt256,
+    owner String,
+    amount UInt256,
+    unlock_time UInt64,
+    permanent Bool
+)
+ENGINE = ReplacingMergeTree()
+ORDER BY token_id;
+
+-- veAERO votes (Voter Voted/Abstained)
+CREATE TABLE IF NOT EXISTS votes (
+    id String,
+    tx_hash String,
+    log_index UInt64,
+    block_number UInt64,
+    timestamp UInt64,
+    epoch UInt64,
+    action String,
+    voter String,
+    token_id UInt256,
+    pool_address String,
+    weight UInt256,
+    pool_weight UInt256
+)
+ENGINE = MergeTree()
+ORDER BY (pool_address, epoch, block_number, log_index)
+PARTITION BY toYYYYMM(toDateTime(timestamp));
+
+-- Pool vote weight at the end of each epoch it changed in
+CREATE TABLE IF NOT EXISTS pool_votes (
+    epoch UInt64,
+    pool_address String,
+    weight UInt256
+)
+ENGINE = ReplacingMergeTree()
+ORDER BY (epoch, pool_address);
+
+-- Gauges created by the Voter
+CREATE TABLE IF NOT EXISTS gauges (
+    id String,
+    pool_address String,
+    bribe_voting_reward String,
+    fees_voting_reward String,
+    creator String,
+    alive Bool,
+    created_block UInt64,
+    created_timestamp UInt64,
+    staked_supply UInt256 DEFAULT 0,
+    total_emitted UInt256 DEFAULT 0,
+    total_claimed UInt256 DEFAULT 0
+)
+ENGINE = ReplacingMergeTree()
+ORDER BY id;
+
+-- Gauge staking, claims and AERO emissions
+CREATE TABLE IF NOT EXISTS gauge_events (
+    id String,
+    tx_hash String,
+    log_index UInt64,
+    block_number UInt64,
+    timestamp UInt64,
+    epoch UInt64,
+    gauge String,
+    pool_address String,
+    action String,
+    account String,
+    amount UInt256
+)
+ENGINE = MergeTree()
+ORDER BY (gauge, block_number, log_index)
+PARTITION BY toYYYYMM(toDateTime(timestamp));
+
+-- AERO emitted per gauge per epoch
+CREATE TABLE IF NOT EXISTS gauge_emissions (
+    epoch UInt64,
+    gauge String,
+    emitted UInt256
+)
+ENGINE = ReplacingMergeTree()
+ORDER BY (epoch, gauge);
+
+-- Fees and bribes per pool, reward token and epoch
+CREATE TABLE IF NOT EXISTS voting_rewards (
+    epoch UInt64,
+    pool_address String,
+    reward_type String,
+    token String,
+    notified UInt256 DEFAULT 0,
+    claimed UInt256 DEFAULT 0
+)
+ENGINE = ReplacingMergeTree()
+ORDER BY (epoch, pool_address, reward_type, token);
+
+-- AERO emissions, tail emission rate and veAERO rebases per epoch
+CREATE TABLE IF NOT EXISTS epoch_emissions (
+    epoch UInt64,
+    epoch_start UInt64,
+    emissions Nullable(UInt256),
+    circulating_supply Nullable(UInt256),
+    tail Nullable(Bool),
+    tail_emission_rate Nullable(UInt256),
+    rebase UInt256 DEFAULT 0,
+    rebase_claimed UInt256 DEFAULT 0,
+    block_number Nullable(UInt64)
+)
+ENGINE = ReplacingMergeTree()
+ORDER BY epoch;
+
+-- Pool reserves and USD TVL, one row per pool per block with a Sync
+CREATE TABLE IF NOT EXISTS pool_tvl (
+    id String,
+    pool_address String,
+    block_number UInt64,
+    timestamp UInt64,
+    token0 String,
+    token1 String,
+    reserve0 UInt256,
+    reserve1 UInt256,
+    reserve0_decimal Decimal256(38),
+    reserve1_decimal Decimal256(38),
+    tvl_usd Nullable(Decimal256(38)) -- NULL when neither token is priced
+)
+ENGINE = MergeTree()
+ORDER BY (pool_address, block_number);
+
+-- Protocol TVL in USD after every block with a Sync
+CREATE TABLE IF NOT EXISTS protocol_tvl (
+    block_number UInt64,
+    timestamp UInt64,
+    tvl_usd Decimal256(38),
+    pools_changed UInt64
+)
+ENGINE = ReplacingMergeTree()
+ORDER BY block_number;
+
+-- Pool reserves, activity and TVL per day; closed once a later day opens
+CREATE TABLE IF NOT EXISTS pool_day_data (
+    date String,
+    pool_address String,
+    period_start UInt64,
+    open_reserve0 UInt256,
+    open_reserve1 UInt256,
+    close_reserve0 UInt256,
+    close_reserve1 UInt256,
+    volume0 UInt256 DEFAULT 0, -- token0 traded in or out (raw units)
+    volume1 UInt256 DEFAULT 0,
+    volume_usd Decimal256(38) DEFAULT 0,
+    fees0 UInt256 DEFAULT 0,
+    fees1 UInt256 DEFAULT 0,
+    swap_count Int64 DEFAULT 0,
+    unique_traders Int64 DEFAULT 0,
+    tvl_usd Nullable(Decimal256(38)), -- NULL when neither token is priced
+    closed Bool DEFAULT false
+)
+ENGINE = ReplacingMergeTree()
+ORDER BY (date, pool_address);
+
+-- Pool reserves, activity and TVL per hour; closed once a later hour opens
+CREATE TABLE IF NOT EXISTS pool_hour_data (
+    hour String,
+    pool_address String,
+    period_start UInt64,
+    open_reserve0 UInt256,
+    open_reserve1 UInt256,
+    close_reserve0 UInt256,
+    close_reserve1 UInt256,
+    volume0 UInt256 DEFAULT 0, -- token0 traded in or out (raw units)
+    volume1 UInt256 DEFAULT 0,
+    volume_usd Decimal256(38) DEFAULT 0,
+    fees0 UInt256 DEFAULT 0,
+    fees1 UInt256 DEFAULT 0,
+    swap_count Int64 DEFAULT 0,
+    unique_traders Int64 DEFAULT 0,
+    tvl_usd Nullable(Decimal256(38)), -- NULL when neither token is priced
+    closed Bool DEFAULT false
+)
+ENGINE = ReplacingMergeTree()
+ORDER BY (hour, pool_address);
+
+-- Pool fee rates set at creation, by SetCustomFee and by setFee (defaults)
+CREATE TABLE IF NOT EXISTS pool_fee_history (
+    id String,
+    block_number UInt64,
+    timestamp UInt64,
+    tx_hash String,
+    action String,
+    pool_address Nullable(String), -- NULL for default fee changes
+    stable Bool,
+    fee UInt64, -- value set, 0 = default, 420 = zero fee
+    fee_rate UInt64 -- basis points in effect afterwards
+)
+ENGINE = MergeTree()
+ORDER BY (block_number, id);
+
+-- Tokens whitelisted for gauge rewards and bribes
+CREATE TABLE IF NOT EXISTS whitelisted_tokens (
+    id String,
+    whitelisted Bool,
+    block_number UInt64
+)
+ENGINE = ReplacingMergeTree(block_number)
+ORDER BY id;
+
+-- Pool reserve history (Sync events)
+CREATE TABLE IF NOT EXISTS aerodrome_pool_reserves (
+    id String,
+    tx_hash String,
+    log_index UInt64,
+    block_number UInt64,
+    timestamp Int64,
+    pool_address String,
+    reserve0 UInt256,
+    reserve1 UInt256
+)
+ENGINE = MergeTree()
+ORDER BY (pool_address, block_number, log_index)
+PARTITION BY toYYYYMM(toDateTime(timestamp));
+
+-- Token metadata resolved via eth_call at pool creation
+CREATE TABLE IF NOT EXISTS tokens (
+    id String,
+    name String,
+    symbol String,
+    decimals UInt64,
+    price_usd Nullable(Decimal256(38)) -- from its deepest pool against USDC, USDbC or WETH
+)
+ENGINE = ReplacingMergeTree()
+ORDER BY id;
+
 -- ====================
 -- OHLCV Candles (Raw)
 -- ====================
 
--- Raw candle data from substreams
+-- Raw candle data from substreams, every column rewritten on each change
 CREATE TABLE IF NOT EXISTS candles (
     pool_address String,
     interval_seconds Int64,
     timestamp Int64,
-    open Int64,
-    high Int64,
-    low Int64,
-    close Int64,
-    volume_in String,
-    volume_out String,
-    trade_count Int64
+    open Nullable(Decimal256(38)),
+    high Nullable(Decimal256(38)),
+    low Nullable(Decimal256(38)),
+    close Nullable(Decimal256(38)),
+    volume0 Decimal256(38),
+    volume1 Decimal256(38),
+    trade_count Int64,
+    closed Bool DEFAULT false -- a later candle has opened
 )
 ENGINE = ReplacingMergeTree()
 ORDER BY (pool_address, interval_seconds, timestamp);
@@ -48,198 +426,186 @@ ORDER BY (pool_address, interval_seconds, timestamp);
 -- Aggregation Tables
 -- ====================
 
+-- db_out upserts only the columns a block changed, which ClickHouse inserts as
+-- rows with every other column at its default. All values are running totals,
+-- so each column keeps its maximum and partial rows merge into the full row;
+-- read these tables with FINAL or max().
+
 -- Pool statistics
 CREATE TABLE IF NOT EXISTS pool_stats (
     pool_address String,
-    swap_count Int64,
-    total_volume String,
-    last_swap_block UInt64,
-    last_swap_time Int64
+    swap_count SimpleAggregateFunction(max, Int64) DEFAULT 0,
+    total_volume0 SimpleAggregateFunction(max, UInt256) DEFAULT 0,
+    total_volume1 SimpleAggregateFunction(max, UInt256) DEFAULT 0,
+    total_volume_usd SimpleAggregateFunction(max, Decimal256(38)) DEFAULT 0,
+    total_fees0 SimpleAggregateFunction(max, UInt256) DEFAULT 0,
+    total_fees1 SimpleAggregateFunction(max, UInt256) DEFAULT 0,
+    last_swap_block SimpleAggregateFunction(max, UInt64) DEFAULT 0,
+    last_swap_time SimpleAggregateFunction(max, Int64) DEFAULT 0,
+    unique_traders SimpleAggregateFunction(max, Int64) DEFAULT 0
 )
-ENGINE = ReplacingMergeTree(last_swap_time)
+ENGINE = AggregatingMergeTree()
 ORDER BY pool_address;
 
 -- Trader statistics
 CREATE TABLE IF NOT EXISTS trader_stats (
     wallet_address String,
-    total_swaps Int64,
-    total_volume String,
-    last_swap_time Int64
+    total_swaps SimpleAggregateFunction(max, Int64) DEFAULT 0,
+    total_volume_usd SimpleAggregateFunction(max, Decimal256(38)) DEFAULT 0,
+    last_swap_time SimpleAggregateFunction(max, Int64) DEFAULT 0
 )
-ENGINE = ReplacingMergeTree(last_swap_time)
+ENGINE = AggregatingMergeTree()
 ORDER BY wallet_address;
 
 -- Daily statistics
 CREATE TABLE IF NOT EXISTS daily_stats (
     date String,
-    swap_count Int64,
-    total_volume String
+    swap_count SimpleAggregateFunction(max, Int64) DEFAULT 0,
+    total_volume_usd SimpleAggregateFunction(max, Decimal256(38)) DEFAULT 0,
+    unique_traders SimpleAggregateFunction(max, Int64) DEFAULT 0,
+    new_traders SimpleAggregateFunction(max, Int64) DEFAULT 0,
+    returning_traders SimpleAggregateFunction(max, Int64) DEFAULT 0
 )
-ENGINE = ReplacingMergeTree()
+ENGINE = AggregatingMergeTree()
 ORDER BY date;
 
 -- Hourly statistics
 CREATE TABLE IF NOT EXISTS hourly_stats (
     hour String,
-    swap_count Int64,
-    total_volume String
+    swap_count SimpleAggregateFunction(max, Int64) DEFAULT 0,
+    total_volume_usd SimpleAggregateFunction(max, Decimal256(38)) DEFAULT 0
 )
-ENGINE = ReplacingMergeTree()
+ENGINE = AggregatingMergeTree()
 ORDER BY hour;
 
--- Protocol metrics
-CREATE TABLE IF NOT EXISTS protocol_metrics (
-    protocol String,
-    total_swaps Int64,
-    total_volume String
+-- ISO week statistics (YYYY-Www)
+CREATE TABLE IF NOT EXISTS weekly_stats (
+    week String,
+    swap_count SimpleAggregateFunction(max, Int64) DEFAULT 0,
+    total_volume_usd SimpleAggregateFunction(max, Decimal256(38)) DEFAULT 0,
+    unique_traders SimpleAggregateFunction(max, Int64) DEFAULT 0
 )
-ENGINE = ReplacingMergeTree()
-ORDER BY protocol;
+ENGINE = AggregatingMergeTree()
+ORDER BY week;
 
--- ====================
--- Materialized Views for Candle Aggregation
--- ====================
+-- Calendar month statistics (YYYY-MM)
+CREATE TABLE IF NOT EXISTS monthly_stats (
+    month String,
+    swap_count SimpleAggregateFunction(max, Int64) DEFAULT 0,
+    total_volume_usd SimpleAggregateFunction(max, Decimal256(38)) DEFAULT 0,
+    unique_traders SimpleAggregateFunction(max, Int64) DEFAULT 0
+)
+ENGINE = AggregatingMergeTree()
+ORDER BY month;
 
--- Hourly candle aggregation from swaps
-CREATE MATERIALIZED VIEW IF NOT EXISTS mv_candles_1h
+-- Aerodrome epoch statistics (weeks flipping Thursday 00:00 UTC)
+CREATE TABLE IF NOT EXISTS epoch_stats (
+    epoch UInt64,
+    epoch_start SimpleAggregateFunction(max, UInt64) DEFAULT 0,
+    swap_count SimpleAggregateFunction(max, Int64) DEFAULT 0,
+    total_volume_usd SimpleAggregateFunction(max, Decimal256(38)) DEFAULT 0,
+    unique_traders SimpleAggregateFunction(max, Int64) DEFAULT 0
+)
 ENGINE = AggregatingMergeTree()
-ORDER BY (pool_address, timestamp)
-AS SELECT
-    pool_address,
-    3600 AS interval_seconds,
-    toStartOfHour(toDateTime(timestamp)) AS timestamp,
-    argMinState(
-        toInt64(toUInt64OrZero(amount0_out) * 1000000 / greatest(toUInt64OrZero(amount0_in), 1)),
-        timestamp
-    ) AS open_state,
-    argMaxState(
-        toInt64(toUInt64OrZero(amount0_out) * 1000000 / greatest(toUInt64OrZero(amount0_in), 1)),
-        timestamp
-    ) AS close_state,
-    maxState(toInt64(toUInt64OrZero(amount0_out) * 1000000 / greatest(toUInt64OrZero(amount0_in), 1))) AS high_state,
-    minState(toInt64(toUInt64OrZero(amount0_out) * 1000000 / greatest(toUInt64OrZero(amount0_in), 1))) AS low_state,
-    sumState(toUInt64OrZero(amount0_in) + toUInt64OrZero(amount1_in)) AS volume_in_state,
-    sumState(toUInt64OrZero(amount0_out) + toUInt64OrZero(amount1_out)) AS volume_out_state,
-    countState() AS trade_count_state
-FROM aerodrome_swaps
-GROUP BY pool_address, timestamp;
+ORDER BY epoch;
 
--- Daily candle aggregation from swaps
-CREATE MATERIALIZED VIEW IF NOT EXISTS mv_candles_1d
+-- Per-pool epoch statistics
+CREATE TABLE IF NOT EXISTS pool_epoch_stats (
+    epoch UInt64,
+    pool_address String,
+    swap_count SimpleAggregateFunction(max, Int64) DEFAULT 0,
+    total_volume0 SimpleAggregateFunction(max, UInt256) DEFAULT 0,
+    total_volume1 SimpleAggregateFunction(max, UInt256) DEFAULT 0,
+    total_volume_usd SimpleAggregateFunction(max, Decimal256(38)) DEFAULT 0,
+    total_fees0 SimpleAggregateFunction(max, UInt256) DEFAULT 0,
+    total_fees1 SimpleAggregateFunction(max, UInt256) DEFAULT 0
+)
 ENGINE = AggregatingMergeTree()
-ORDER BY (pool_address, timestamp)
-AS SELECT
-    pool_address,
-    86400 AS interval_seconds,
-    toStartOfDay(toDateTime(timestamp)) AS timestamp,
-    argMinState(
-        toInt64(toUInt64OrZero(amount0_out) * 1000000 / greatest(toUInt64OrZero(amount0_in), 1)),
-        timestamp
-    ) AS open_state,
-    argMaxState(
-        toInt64(toUInt64OrZero(amount0_out) * 1000000 / greatest(toUInt64OrZero(amount0_in), 1)),
-        timestamp
-    ) AS close_state,
-    maxState(toInt64(toUInt64OrZero(amount0_out) * 1000000 / greatest(toUInt64OrZero(amount0_in), 1))) AS high_state,
-    minState(toInt64(toUInt64OrZero(amount0_out) * 1000000 / greatest(toUInt64OrZero(amount0_in), 1))) AS low_state,
-    sumState(toUInt64OrZero(amount0_in) + toUInt64OrZero(amount1_in)) AS volume_in_state,
-    sumState(toUInt64OrZero(amount0_out) + toUInt64OrZero(amount1_out)) AS volume_out_state,
-    countState() AS trade_count_state
-FROM aerodrome_swaps
-GROUP BY pool_address, timestamp;
+ORDER BY (epoch, pool_address);
 
--- Pool volume materialized view
-CREATE MATERIALIZED VIEW IF NOT EXISTS mv_pool_volume
-ENGINE = SummingMergeTree()
-ORDER BY pool_address
-AS SELECT
-    pool_address,
-    count() AS swap_count,
-    sum(toUInt64OrZero(amount0_in) + toUInt64OrZero(amount1_in)) AS total_volume,
-    max(block_number) AS last_block,
-    max(timestamp) AS last_swap_time
-FROM aerodrome_swaps
-GROUP BY pool_address;
-
--- Trader volume materialized view
-CREATE MATERIALIZED VIEW IF NOT EXISTS mv_trader_volume
-ENGINE = SummingMergeTree()
-ORDER BY wallet_address
-AS SELECT
-    sender AS wallet_address,
-    count() AS total_swaps,
-    sum(toUInt64OrZero(amount0_in) + toUInt64OrZero(amount1_in)) AS total_volume,
-    max(timestamp) AS last_swap_time
-FROM aerodrome_swaps
-GROUP BY sender;
+-- Per-pool daily statistics, LP fees in raw units of each pool token
+CREATE TABLE IF NOT EXISTS pool_daily_stats (
+    date String,
+    pool_address String,
+    swap_count SimpleAggregateFunction(max, Int64) DEFAULT 0,
+    total_volume0 SimpleAggregateFunction(max, UInt256) DEFAULT 0,
+    total_volume1 SimpleAggregateFunction(max, UInt256) DEFAULT 0,
+    total_volume_usd SimpleAggregateFunction(max, Decimal256(38)) DEFAULT 0,
+    total_fees0 SimpleAggregateFunction(max, UInt256) DEFAULT 0,
+    total_fees1 SimpleAggregateFunction(max, UInt256) DEFAULT 0
+)
+ENGINE = AggregatingMergeTree()
+ORDER BY (date, pool_address);
 
--- Daily volume materialized view
-CREATE MATERIALIZED VIEW IF NOT EXISTS mv_daily_volume
-ENGINE = SummingMergeTree()
-ORDER BY date
-AS SELECT
-    toDate(toDateTime(timestamp)) AS date,
-    count() AS swap_count,
-    sum(toUInt64OrZero(amount0_in) + toUInt64OrZero(amount1_in)) AS total_volume
-FROM aerodrome_swaps
-GROUP BY date;
+-- Protocol metrics
+CREATE TABLE IF NOT EXISTS protocol_metrics (
+    protocol String,
+    total_swaps SimpleAggregateFunction(max, Int64) DEFAULT 0,
+    total_volume_usd SimpleAggregateFunction(max, Decimal256(38)) DEFAULT 0,
+    total_traders SimpleAggregateFunction(max, Int64) DEFAULT 0
+)
+ENGINE = AggregatingMergeTree()
+ORDER BY protocol;
 
 -- ====================
 -- Query Views
 -- ====================
 
--- Finalized hourly candles
+-- Hourly candles, closed once a later hour opens
 CREATE VIEW IF NOT EXISTS v_candles_1h AS
 SELECT
     pool_address,
-    interval_seconds,
     timestamp,
-    argMinMerge(open_state) AS open,
-    argMaxMerge(close_state) AS close,
-    maxMerge(high_state) AS high,
-    minMerge(low_state) AS low,
-    sumMerge(volume_in_state) AS volume_in,
-    sumMerge(volume_out_state) AS volume_out,
-    countMerge(trade_count_state) AS trade_count
-FROM mv_candles_1h
-GROUP BY pool_address, interval_seconds, timestamp;
-
--- Finalized daily candles
+    open,
+    high,
+    low,
+    close,
+    volume0,
+    volume1,
+    trade_count,
+    closed
+FROM candles FINAL
+WHERE interval_seconds = 3600;
+
+-- Daily candles, closed once a later day opens
 CREATE VIEW IF NOT EXISTS v_candles_1d AS
 SELECT
     pool_address,
-    interval_seconds,
     timestamp,
-    argMinMerge(open_state) AS open,
-    argMaxMerge(close_state) AS close,
-    maxMerge(high_state) AS high,
-    minMerge(low_state) AS low,
-    sumMerge(volume_in_state) AS volume_in,
-    sumMerge(volume_out_state) AS volume_out,
-    countMerge(trade_count_state) AS trade_count
-FROM mv_candles_1d
-GROUP BY pool_address, interval_seconds, timestamp;
-
--- Top pools by volume
+    open,
+    high,
+    low,
+    close,
+    volume0,
+    volume1,
+    trade_count,
+    closed
+FROM candles FINAL
+WHERE interval_seconds = 86400;
+
+-- Top pools by USD volume
 CREATE VIEW IF NOT EXISTS v_top_pools AS
 SELECT
     pool_address,
-    sum(swap_count) AS total_swaps,
-    sum(total_volume) AS total_volume
-FROM mv_pool_volume
-GROUP BY pool_address
-ORDER BY total_volume DESC
+    swap_count,
+    total_volume0,
+    total_volume1,
+    total_volume_usd,
+    total_fees0,
+    total_fees1
+FROM pool_stats FINAL
+ORDER BY total_volume_usd DESC
 LIMIT 100;
 
--- Top traders by volume
+-- Top traders by USD volume
 CREATE VIEW IF NOT EXISTS v_top_traders AS
 SELECT
     wallet_address,
-    sum(total_swaps) AS total_swaps,
-    sum(total_volume) AS total_volume
-FROM mv_trader_volume
-GROUP BY wallet_address
-ORDER BY total_volume DESC
+    total_swaps,
+    total_volume_usd,
+    toDateTime(last_swap_time) AS last_active
+FROM trader_stats FINAL
+ORDER BY total_volume_usd DESC
 LIMIT 100;
 
 -- Daily unique traders
@@ -252,17 +618,17 @@ FROM aerodrome_swaps
 GROUP BY date
 ORDER BY date DESC;
 
--- Pool activity summary
+-- Pool activity summary, volumes in raw units of each pool token
 CREATE VIEW IF NOT EXISTS v_pool_activity AS
 SELECT
     pool_address,
-    count() AS swap_count,
-    uniqExact(sender) AS unique_traders,
-    sum(toUInt64OrZero(amount0_in) + toUInt64OrZero(amount1_in)) AS total_volume,
-    min(timestamp) AS first_swap,
-    max(timestamp) AS last_swap
-FROM aerodrome_swaps
-GROUP BY pool_address
+    swap_count,
+    unique_traders,
+    total_volume0,
+    total_volume1,
+    total_volume_usd,
+    toDateTime(last_swap_time) AS last_swap
+FROM pool_stats FINAL
 ORDER BY swap_count DESC;
 
 -- Recent large swaps (whale activity)
@@ -278,6 +644,6 @@ SELECT
     amount1_out,
     toDateTime(timestamp) AS swap_time
 FROM aerodrome_swaps
-WHERE toUInt64OrZero(amount0_in) + toUInt64OrZero(amount1_in) > 1000000000000
+WHERE amount_in > 1000000000000
 ORDER BY timestamp DESC
 LIMIT 100;
@@ -15,19 +15,387 @@ CREATE TABLE IF NOT EXISTS aerodrome_swaps (
     pool_address VARCHAR NOT NULL,
     sender VARCHAR NOT NULL,
     recipient VARCHAR NOT NULL,
-    amount0_in VARCHAR NOT NULL,
-    amount1_in VARCHAR NOT NULL,
-    amount0_out VARCHAR NOT NULL,
-    amount1_out VARCHAR NOT NULL,
-    amount_in_total BIGINT NOT NULL,
-    amount_out_total BIGINT NOT NULL,
-    price_ratio BIGINT NOT NULL
+    amount0_in NUMERIC NOT NULL,
+    amount1_in NUMERIC NOT NULL,
+    amount0_out NUMERIC NOT NULL,
+    amount1_out NUMERIC NOT NULL,
+    token0 VARCHAR NOT NULL,
+    token1 VARCHAR NOT NULL,
+    amount0_in_decimal NUMERIC NOT NULL,
+    amount1_in_decimal NUMERIC NOT NULL,
+    amount0_out_decimal NUMERIC NOT NULL,
+    amount1_out_decimal NUMERIC NOT NULL,
+    token_in VARCHAR NOT NULL,
+    token_out VARCHAR NOT NULL,
+    amount_in NUMERIC NOT NULL,
+    amount_out NUMERIC NOT NULL,
+    amount_in_decimal NUMERIC NOT NULL,
+    amount_out_decimal NUMERIC NOT NULL,
+    two_sided BOOLEAN NOT NULL,
+    -- Decimal-adjusted prices, NULL when undefined
+    price0 NUMERIC,
+    price1 NUMERIC,
+    mid_price0 NUMERIC,
+    mid_price1 NUMERIC,
+    price_impact NUMERIC,
+    amount_usd NUMERIC, -- NULL when neither token is priced
+    fee_rate BIGINT NOT NULL, -- basis points
+    -- LP fees charged on the input side(s)
+    fee0 NUMERIC NOT NULL,
+    fee1 NUMERIC NOT NULL,
+    fee0_decimal NUMERIC NOT NULL,
+    fee1_decimal NUMERIC NOT NULL
 );
 
 CREATE INDEX IF NOT EXISTS idx_swaps_pool ON aerodrome_swaps(pool_address);
 CREATE INDEX IF NOT EXISTS idx_swaps_timestamp ON aerodrome_swaps(timestamp DESC);
 CREATE INDEX IF NOT EXISTS idx_swaps_sender ON aerodrome_swaps(sender);
 CREATE INDEX IF NOT EXISTS idx_swaps_block ON aerodrome_swaps(block_number DESC);
+CREATE INDEX IF NOT EXISTS idx_swaps_token_in ON aerodrome_swaps(token_in);
+CREATE INDEX IF NOT EXISTS idx_swaps_token_out ON aerodrome_swaps(token_out);
+
+-- Slipstream (concentrated liquidity) swap events
+CREATE TABLE IF NOT EXISTS aerodrome_cl_swaps (
+    id VARCHAR PRIMARY KEY,
+    tx_hash VARCHAR NOT NULL,
+    log_index BIGINT NOT NULL,
+    block_number BIGINT NOT NULL,
+    timestamp BIGINT NOT NULL,
+    pool_address VARCHAR NOT NULL,
+    tick_spacing INTEGER NOT NULL,
+    sender VARCHAR NOT NULL,
+    recipient VARCHAR NOT NULL,
+    token0 VARCHAR NOT NULL,
+    token1 VARCHAR NOT NULL,
+    -- Signed pool balance deltas, positive when paid into the pool
+    amount0 NUMERIC NOT NULL,
+    amount1 NUMERIC NOT NULL,
+    amount0_decimal NUMERIC NOT NULL,
+    amount1_decimal NUMERIC NOT NULL,
+    token_in VARCHAR NOT NULL,
+    token_out VARCHAR NOT NULL,
+    amount_in NUMERIC NOT NULL,
+    amount_out NUMERIC NOT NULL,
+    amount_in_decimal NUMERIC NOT NULL,
+    amount_out_decimal NUMERIC NOT NULL,
+    sqrt_price_x96 NUMERIC NOT NULL,
+    liquidity NUMERIC NOT NULL,
+    tick INTEGER NOT NULL,
+    price0 NUMERIC,
+    price1 NUMERIC,
+    pool_price0 NUMERIC
+);
+
+CREATE INDEX IF NOT EXISTS idx_cl_swaps_pool ON aerodrome_cl_swaps(pool_address);
+CREATE INDEX IF NOT EXISTS idx_cl_swaps_timestamp ON aerodrome_cl_swaps(timestamp DESC);
+CREATE INDEX IF NOT EXISTS idx_cl_swaps_sender ON aerodrome_cl_swaps(sender);
+
+-- Liquidity events (Mint/Burn)
+CREATE TABLE IF NOT EXISTS aerodrome_liquidity_events (
+    id VARCHAR PRIMARY KEY,
+    tx_hash VARCHAR NOT NULL,
+    log_index BIGINT NOT NULL,
+    block_number BIGINT NOT NULL,
+    timestamp BIGINT NOT NULL,
+    pool_address VARCHAR NOT NULL,
+    action VARCHAR NOT NULL, -- 'mint', 'burn' or 'collect'
+    sender VARCHAR NOT NULL,
+    recipient VARCHAR NOT NULL,
+    token0 VARCHAR NOT NULL,
+    token1 VARCHAR NOT NULL,
+    amount0 NUMERIC NOT NULL,
+    amount1 NUMERIC NOT NULL,
+    amount0_decimal NUMERIC NOT NULL,
+    amount1_decimal NUMERIC NOT NULL,
+    -- Slipstream pools only ('collect' is Slipstream only as well)
+    tick_lower INTEGER,
+    tick_upper INTEGER,
+    liquidity NUMERIC
+);
+
+CREATE INDEX IF NOT EXISTS idx_liquidity_pool ON aerodrome_liquidity_events(pool_address);
+CREATE INDEX IF NOT EXISTS idx_liquidity_timestamp ON aerodrome_liquidity_events(timestamp DESC);
+CREATE INDEX IF NOT EXISTS idx_liquidity_sender ON aerodrome_liquidity_events(sender);
+
+-- Slipstream LP positions (NonfungiblePositionManager NFTs)
+CREATE TABLE IF NOT EXISTS cl_positions (
+    token_id VARCHAR PRIMARY KEY,
+    pool_address VARCHAR NOT NULL,
+    token0 VARCHAR NOT NULL,
+    token1 VARCHAR NOT NULL,
+    tick_lower INTEGER NOT NULL,
+    tick_upper INTEGER NOT NULL,
+    owner VARCHAR NOT NULL,
+    liquidity NUMERIC NOT NULL,
+    collected0 NUMERIC NOT NULL, -- principal and fees collected
+    collected1 NUMERIC NOT NULL,
+    withdrawn0 NUMERIC NOT NULL, -- principal released by DecreaseLiquidity
+    withdrawn1 NUMERIC NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_cl_positions_owner ON cl_positions(owner);
+CREATE INDEX IF NOT EXISTS idx_cl_positions_pool ON cl_positions(pool_address);
+
+-- Slipstream liquidityNet per initialized tick
+CREATE TABLE IF NOT EXISTS cl_ticks (
+    pool_address VARCHAR NOT NULL,
+    tick INTEGER NOT NULL,
+    liquidity_net NUMERIC NOT NULL,
+    block_number BIGINT NOT NULL,
+    PRIMARY KEY (pool_address, tick)
+);
+
+-- Slipstream pool active liquidity and price
+CREATE TABLE IF NOT EXISTS cl_pool_state (
+    pool_address VARCHAR PRIMARY KEY,
+    liquidity NUMERIC NOT NULL,
+    sqrt_price_x96 NUMERIC NOT NULL,
+    tick INTEGER NOT NULL,
+    block_number BIGINT NOT NULL,
+    timestamp BIGINT NOT NULL
+);
+
+-- veAERO lock events (VotingEscrow)
+CREATE TABLE IF NOT EXISTS ve_lock_events (
+    id VARCHAR PRIMARY KEY,
+    tx_hash VARCHAR NOT NULL,
+    log_index BIGINT NOT NULL,
+    block_number BIGINT NOT NULL,
+    timestamp BIGINT NOT NULL,
+    token_id NUMERIC NOT NULL,
+    action VARCHAR NOT NULL,
+    owner VARCHAR NOT NULL,
+    amount NUMERIC NOT NULL,
+    unlock_time BIGINT NOT NULL,
+    deposit_type VARCHAR NOT NULL,
+    related_token_id VARCHAR NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_ve_lock_events_token ON ve_lock_events(token_id);
+CREATE INDEX IF NOT EXISTS idx_ve_lock_events_owner ON ve_lock_events(owner);
+
+-- Current veAERO locks by NFT token id
+CREATE TABLE IF NOT EXISTS ve_locks (
+    token_id NUMERIC PRIMARY KEY,
+    owner VARCHAR NOT NULL,
+    amount NUMERIC NOT NULL,
+    unlock_time BIGINT NOT NULL,
+    permanent BOOLEAN NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_ve_locks_owner ON ve_locks(owner);
+
+-- veAERO votes (Voter Voted/Abstained)
+CREATE TABLE IF NOT EXISTS votes (
+    id VARCHAR PRIMARY KEY,
+    tx_hash VARCHAR NOT NULL,
+    log_index BIGINT NOT NULL,
+    block_number BIGINT NOT NULL,
+    timestamp BIGINT NOT NULL,
+    epoch BIGINT NOT NULL,
+    action VARCHAR NOT NULL,
+    voter VARCHAR NOT NULL,
+    token_id NUMERIC NOT NULL,
+    pool_address VARCHAR NOT NULL,
+    weight NUMERIC NOT NULL,
+    pool_weight NUMERIC NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_votes_pool_epoch ON votes(pool_address, epoch);
+CREATE INDEX IF NOT EXISTS idx_votes_token ON votes(token_id);
+
+-- Pool vote weight at the end of each epoch it changed in
+CREATE TABLE IF NOT EXISTS pool_votes (
+    epoch BIGINT NOT NULL,
+    pool_address VARCHAR NOT NULL,
+    weight NUMERIC NOT NULL,
+    PRIMARY KEY (epoch, pool_address)
+);
+
+-- Gauges created by the Voter
+CREATE TABLE IF NOT EXISTS gauges (
+    id VARCHAR PRIMARY KEY,
+    pool_address VARCHAR NOT NULL,
+    bribe_voting_reward VARCHAR NOT NULL,
+    fees_voting_reward VARCHAR NOT NULL,
+    creator VARCHAR NOT NULL,
+    alive BOOLEAN NOT NULL,
+    created_block BIGINT NOT NULL,
+    created_timestamp BIGINT NOT NULL,
+    staked_supply NUMERIC NOT NULL DEFAULT 0,
+    total_emitted NUMERIC NOT NULL DEFAULT 0,
+    total_claimed NUMERIC NOT NULL DEFAULT 0
+);
+
+CREATE INDEX IF NOT EXISTS idx_gauges_pool ON gauges(pool_address);
+
+-- Gauge staking, claims and AERO emissions
+CREATE TABLE IF NOT EXISTS gauge_events (
+    id VARCHAR PRIMARY KEY,
+    tx_hash VARCHAR NOT NULL,
+    log_index BIGINT NOT NULL,
+    block_number BIGINT NOT NULL,
+    timestamp BIGINT NOT NULL,
+    epoch BIGINT NOT NULL,
+    gauge VARCHAR NOT NULL,
+    pool_address VARCHAR NOT NULL,
+    action VARCHAR NOT NULL,
+    account VARCHAR NOT NULL,
+    amount NUMERIC NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_gauge_events_gauge ON gauge_events(gauge, block_number DESC);
+CREATE INDEX IF NOT EXISTS idx_gauge_events_account ON gauge_events(account);
+
+-- AERO emitted per gauge per epoch
+CREATE TABLE IF NOT EXISTS gauge_emissions (
+    epoch BIGINT NOT NULL,
+    gauge VARCHAR NOT NULL,
+    emitted NUMERIC NOT NULL,
+    PRIMARY KEY (epoch, gauge)
+);
+
+-- Fees and bribes per pool, reward token and epoch
+CREATE TABLE IF NOT EXISTS voting_rewards (
+    epoch BIGINT NOT NULL,
+    pool_address VARCHAR NOT NULL,
+    reward_type VARCHAR NOT NULL,
+    token VARCHAR NOT NULL,
+    notified NUMERIC NOT NULL DEFAULT 0,
+    claimed NUMERIC NOT NULL DEFAULT 0,
+    PRIMARY KEY (epoch, pool_address, reward_type, token)
+);
+
+CREATE INDEX IF NOT EXISTS idx_voting_rewards_pool ON voting_rewards(pool_address, epoch DESC);
+
+-- AERO emissions, tail emission rate and veAERO rebases per epoch
+CREATE TABLE IF NOT EXISTS epoch_emissions (
+    epoch BIGINT PRIMARY KEY,
+    epoch_start BIGINT NOT NULL,
+    emissions NUMERIC,
+    circulating_supply NUMERIC,
+    tail BOOLEAN,
+    tail_emission_rate NUMERIC,
+    rebase NUMERIC DEFAULT 0,
+    rebase_claimed NUMERIC DEFAULT 0,
+    block_number BIGINT
+);
+
+-- Pool reserves and USD TVL, one row per pool per block with a Sync
+CREATE TABLE IF NOT EXISTS pool_tvl (
+    id VARCHAR PRIMARY KEY,
+    pool_address VARCHAR NOT NULL,
+    block_number BIGINT NOT NULL,
+    timestamp BIGINT NOT NULL,
+    token0 VARCHAR NOT NULL,
+    token1 VARCHAR NOT NULL,
+    reserve0 NUMERIC NOT NULL,
+    reserve1 NUMERIC NOT NULL,
+    reserve0_decimal NUMERIC NOT NULL,
+    reserve1_decimal NUMERIC NOT NULL,
+    tvl_usd NUMERIC -- NULL when neither token is priced
+);
+
+CREATE INDEX IF NOT EXISTS idx_pool_tvl_pool ON pool_tvl(pool_address, block_number DESC);
+
+-- Protocol TVL in USD after every block with a Sync
+CREATE TABLE IF NOT EXISTS protocol_tvl (
+    block_number BIGINT PRIMARY KEY,
+    timestamp BIGINT NOT NULL,
+    tvl_usd NUMERIC NOT NULL,
+    pools_changed BIGINT NOT NULL
+);
+
+-- Pool reserves, activity and TVL per day; closed once a later day opens
+CREATE TABLE IF NOT EXISTS pool_day_data (
+    date VARCHAR NOT NULL,
+    pool_address VARCHAR NOT NULL,
+    period_start BIGINT NOT NULL,
+    open_reserve0 NUMERIC,
+    open_reserve1 NUMERIC,
+    close_reserve0 NUMERIC,
+    close_reserve1 NUMERIC,
+    volume0 NUMERIC DEFAULT 0, -- token0 traded in or out (raw units)
+    volume1 NUMERIC DEFAULT 0,
+    volume_usd NUMERIC DEFAULT 0,
+    fees0 NUMERIC DEFAULT 0,
+    fees1 NUMERIC DEFAULT 0,
+    swap_count BIGINT DEFAULT 0,
+    unique_traders BIGINT DEFAULT 0,
+    tvl_usd NUMERIC, -- NULL when neither token is priced
+    closed BOOLEAN DEFAULT FALSE,
+    PRIMARY KEY (date, pool_address)
+);
+
+CREATE INDEX IF NOT EXISTS idx_pool_day_data_pool ON pool_day_data(pool_address, period_start DESC);
+
+-- Pool reserves, activity and TVL per hour; closed once a later hour opens
+CREATE TABLE IF NOT EXISTS pool_hour_data (
+    hour VARCHAR NOT NULL,
+    pool_address VARCHAR NOT NULL,
+    period_start BIGINT NOT NULL,
+    open_reserve0 NUMERIC,
+    open_reserve1 NUMERIC,
+    close_reserve0 NUMERIC,
+    close_reserve1 NUMERIC,
+    volume0 NUMERIC DEFAULT 0, -- token0 traded in or out (raw units)
+    volume1 NUMERIC DEFAULT 0,
+    volume_usd NUMERIC DEFAULT 0,
+    fees0 NUMERIC DEFAULT 0,
+    fees1 NUMERIC DEFAULT 0,
+    swap_count BIGINT DEFAULT 0,
+    unique_traders BIGINT DEFAULT 0,
+    tvl_usd NUMERIC, -- NULL when neither token is priced
+    closed BOOLEAN DEFAULT FALSE,
+    PRIMARY KEY (hour, pool_address)
+);
+
+CREATE INDEX IF NOT EXISTS idx_pool_hour_data_pool ON pool_hour_data(pool_address, period_start DESC);
+
+-- Pool fee rates set at creation, by SetCustomFee and by setFee (defaults)
+CREATE TABLE IF NOT EXISTS pool_fee_history (
+    id VARCHAR PRIMARY KEY,
+    block_number BIGINT NOT NULL,
+    timestamp BIGINT NOT NULL,
+    tx_hash VARCHAR NOT NULL,
+    action VARCHAR NOT NULL,
+    pool_address VARCHAR, -- NULL for default fee changes
+    stable BOOLEAN NOT NULL,
+    fee BIGINT NOT NULL, -- value set, 0 = default, 420 = zero fee
+    fee_rate BIGINT NOT NULL -- basis points in effect afterwards
+);
+
+CREATE INDEX IF NOT EXISTS idx_pool_fee_history_pool ON pool_fee_history(pool_address, block_number DESC);
+
+-- Tokens whitelisted for gauge rewards and bribes
+CREATE TABLE IF NOT EXISTS whitelisted_tokens (
+    id VARCHAR PRIMARY KEY,
+    whitelisted BOOLEAN NOT NULL,
+    block_number BIGINT NOT NULL
+);
+
+-- Pool reserve history (Sync events)
+CREATE TABLE IF NOT EXISTS aerodrome_pool_reserves (
+    id VARCHAR PRIMARY KEY,
+    tx_hash VARCHAR NOT NULL,
+    log_index BIGINT NOT NULL,
+    block_number BIGINT NOT NULL,
+    timestamp BIGINT NOT NULL,
+    pool_address VARCHAR NOT NULL,
+    reserve0 NUMERIC NOT NULL,
+    reserve1 NUMERIC NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_reserves_pool_block ON aerodrome_pool_reserves(pool_address, block_number DESC);
+
+-- Token metadata resolved via eth_call at pool creation
+CREATE TABLE IF NOT EXISTS tokens (
+    id VARCHAR PRIMARY KEY,
+    name VARCHAR NOT NULL,
+    symbol VARCHAR NOT NULL,
+    decimals BIGINT NOT NULL,
+    price_usd NUMERIC -- from its deepest pool against USDC, USDbC or WETH
+);
 
 -- ====================
 -- OHLCV Candles
@@ -38,13 +406,14 @@ CREATE TABLE IF NOT EXISTS candles (
     pool_address VARCHAR NOT NULL,
     interval_seconds BIGINT NOT NULL,
     timestamp BIGINT NOT NULL,
-    open BIGINT,
-    high BIGINT,
-    low BIGINT,
-    close BIGINT,
-    volume_in NUMERIC DEFAULT 0,
-    volume_out NUMERIC DEFAULT 0,
+    open NUMERIC,
+    high NUMERIC,
+    low NUMERIC,
+    close NUMERIC,
+    volume0 NUMERIC DEFAULT 0, -- token0 traded (decimal-adjusted)
+    volume1 NUMERIC DEFAULT 0, -- token1 traded (decimal-adjusted)
     trade_count BIGINT DEFAULT 0,
+    closed BOOLEAN DEFAULT FALSE, -- a later candle has opened
     PRIMARY KEY (pool_address, interval_seconds, timestamp)
 );
 
@@ -59,43 +428,105 @@ CREATE INDEX IF NOT EXISTS idx_candles_timestamp ON candles(timestamp DESC);
 CREATE TABLE IF NOT EXISTS pool_stats (
     pool_address VARCHAR PRIMARY KEY,
     swap_count BIGINT DEFAULT 0,
-    total_volume NUMERIC DEFAULT 0,
+    total_volume0 NUMERIC DEFAULT 0,
+    total_volume1 NUMERIC DEFAULT 0,
+    total_volume_usd NUMERIC DEFAULT 0,
+    total_fees0 NUMERIC DEFAULT 0,
+    total_fees1 NUMERIC DEFAULT 0,
     last_swap_block BIGINT,
-    last_swap_time BIGINT
+    last_swap_time BIGINT,
+    unique_traders BIGINT DEFAULT 0
 );
 
-CREATE INDEX IF NOT EXISTS idx_pool_stats_volume ON pool_stats(total_volume DESC);
+CREATE INDEX IF NOT EXISTS idx_pool_stats_volume ON pool_stats(total_volume_usd DESC);
 
 -- Trader statistics
 CREATE TABLE IF NOT EXISTS trader_stats (
     wallet_address VARCHAR PRIMARY KEY,
     total_swaps BIGINT DEFAULT 0,
-    total_volume NUMERIC DEFAULT 0,
+    total_volume_usd NUMERIC DEFAULT 0,
     last_swap_time BIGINT
 );
 
-CREATE INDEX IF NOT EXISTS idx_trader_stats_volume ON trader_stats(total_volume DESC);
+CREATE INDEX IF NOT EXISTS idx_trader_stats_volume ON trader_stats(total_volume_usd DESC);
 CREATE INDEX IF NOT EXISTS idx_trader_stats_swaps ON trader_stats(total_swaps DESC);
 
 -- Daily statistics
 CREATE TABLE IF NOT EXISTS daily_stats (
     date VARCHAR PRIMARY KEY,
     swap_count BIGINT DEFAULT 0,
-    total_volume NUMERIC DEFAULT 0
+    total_volume_usd NUMERIC DEFAULT 0,
+    unique_traders BIGINT DEFAULT 0,
+    new_traders BIGINT DEFAULT 0,
+    returning_traders BIGINT DEFAULT 0
 );
 
 -- Hourly statistics
 CREATE TABLE IF NOT EXISTS hourly_stats (
     hour VARCHAR PRIMARY KEY,
     swap_count BIGINT DEFAULT 0,
-    total_volume NUMERIC DEFAULT 0
+    total_volume_usd NUMERIC DEFAULT 0
+);
+
+-- ISO week statistics (YYYY-Www)
+CREATE TABLE IF NOT EXISTS weekly_stats (
+    week VARCHAR PRIMARY KEY,
+    swap_count BIGINT DEFAULT 0,
+    total_volume_usd NUMERIC DEFAULT 0,
+    unique_traders BIGINT DEFAULT 0
+);
+
+-- Calendar month statistics (YYYY-MM)
+CREATE TABLE IF NOT EXISTS monthly_stats (
+    month VARCHAR PRIMARY KEY,
+    swap_count BIGINT DEFAULT 0,
+    total_volume_usd NUMERIC DEFAULT 0,
+    unique_traders BIGINT DEFAULT 0
 );
 
+-- Aerodrome epoch statistics (weeks flipping Thursday 00:00 UTC)
+CREATE TABLE IF NOT EXISTS epoch_stats (
+    epoch BIGINT PRIMARY KEY,
+    epoch_start BIGINT NOT NULL,
+    swap_count BIGINT DEFAULT 0,
+    total_volume_usd NUMERIC DEFAULT 0,
+    unique_traders BIGINT DEFAULT 0
+);
+
+-- Per-pool epoch statistics
+CREATE TABLE IF NOT EXISTS pool_epoch_stats (
+    epoch BIGINT NOT NULL,
+    pool_address VARCHAR NOT NULL,
+    swap_count BIGINT DEFAULT 0,
+    total_volume0 NUMERIC DEFAULT 0,
+    total_volume1 NUMERIC DEFAULT 0,
+    total_volume_usd NUMERIC DEFAULT 0,
+    total_fees0 NUMERIC DEFAULT 0,
+    total_fees1 NUMERIC DEFAULT 0,
+    PRIMARY KEY (epoch, pool_address)
+);
+
+-- Per-pool daily statistics, LP fees in raw units of each pool token
+CREATE TABLE IF NOT EXISTS pool_daily_stats (
+    date VARCHAR NOT NULL,
+    pool_address VARCHAR NOT NULL,
+    swap_count BIGINT DEFAULT 0,
+    total_volume0 NUMERIC DEFAULT 0,
+    total_volume1 NUMERIC DEFAULT 0,
+    total_volume_usd NUMERIC DEFAULT 0,
+    total_fees0 NUMERIC DEFAULT 0,
+    total_fees1 NUMERIC DEFAULT 0,
+    PRIMARY KEY (date, pool_address)
+);
+
+CREATE INDEX IF NOT EXISTS idx_pool_daily_stats_pool ON pool_daily_stats(pool_address);
+
 -- Protocol-wide metrics
 CREATE TABLE IF NOT EXISTS protocol_metrics (
     protocol VARCHAR PRIMARY KEY,
     total_swaps BIGINT DEFAULT 0,
-    total_volume NUMERIC DEFAULT 0
+    total_volume_usd NUMERIC DEFAULT 0,
+    total_traders BIGINT DEFAULT 0
 );
 
 -- ====================
@@ -112,34 +543,22 @@ SELECT DISTINCT ON (pool_address, interval_seconds)
     high,
     low,
     close,
-    volume_in,
-    volume_out,
-    trade_count
+    volume0,
+    volume1,
+    trade_count,
+    closed
 FROM candles
 ORDER BY pool_address, interval_seconds, timestamp DESC;
 
--- Top pools by volume (24h)
-CREATE OR REPLACE VIEW top_pools_24h AS
-SELECT
-    pool_address,
-    SUM(volume_in) AS volume_24h,
-    SUM(trade_count) AS trades_24h
-FROM candles
-WHERE interval_seconds = 3600
-  AND timestamp > EXTRACT(EPOCH FROM NOW())::BIGINT - 86400
-GROUP BY pool_address
-ORDER BY volume_24h DESC
-LIMIT 50;
-
 -- Top traders by volume
 CREATE OR REPLACE VIEW top_traders AS
 SELECT
     wallet_address,
     total_swaps,
-    total_volume,
+    total_volume_usd,
     TO_TIMESTAMP(last_swap_time) AS last_active
 FROM trader_stats
-ORDER BY total_volume DESC
+ORDER BY total_volume_usd DESC
 LIMIT 100;
 
 -- Daily volume trend (30 days)
@@ -147,7 +566,7 @@ CREATE OR REPLACE VIEW daily_volume_trend AS
 SELECT
     date,
     swap_count,
-    total_volume
+    total_volume_usd
 FROM daily_stats
 ORDER BY date DESC
 LIMIT 30;
@@ -157,7 +576,7 @@ CREATE OR REPLACE VIEW hourly_volume_24h AS
 SELECT
     hour,
     swap_count,
-    total_volume
+    total_volume_usd
 FROM hourly_stats
 ORDER BY hour DESC
 LIMIT 24;
@@ -198,12 +617,12 @@ CREATE OR REPLACE FUNCTION get_candles(
 )
 RETURNS TABLE (
     timestamp BIGINT,
-    open BIGINT,
-    high BIGINT,
-    low BIGINT,
-    close BIGINT,
-    volume_in NUMERIC,
-    volume_out NUMERIC,
+    open NUMERIC,
+    high NUMERIC,
+    low NUMERIC,
+    close NUMERIC,
+    volume0 NUMERIC,
+    volume1 NUMERIC,
     trade_count BIGINT
 ) AS $$
 BEGIN
@@ -214,8 +633,8 @@ BEGIN
         c.high,
         c.low,
         c.close,
-        c.volume_in,
-        c.volume_out,
+        c.volume0,
+        c.volume1,
         c.trade_count
     FROM candles c
     WHERE c.pool_address = p_pool
@@ -230,7 +649,9 @@ CREATE OR REPLACE FUNCTION get_pool_summary(p_pool VARCHAR)
 RETURNS TABLE (
     pool_address VARCHAR,
     swap_count BIGINT,
-    total_volume NUMERIC,
+    total_volume0 NUMERIC,
+    total_volume1 NUMERIC,
+    total_volume_usd NUMERIC,
     volume_24h NUMERIC,
     trades_24h BIGINT,
     last_swap_time TIMESTAMP
@@ -240,8 +661,10 @@ BEGIN
     SELECT
         ps.pool_address,
         ps.swap_count,
-        ps.total_volume,
-        COALESCE(SUM(c.volume_in), 0) AS volume_24h,
+        ps.total_volume0,
+        ps.total_volume1,
+        ps.total_volume_usd,
+        COALESCE(SUM(c.volume0), 0) AS volume_24h,
         COALESCE(SUM(c.trade_count), 0) AS trades_24h,
         TO_TIMESTAMP(ps.last_swap_time) AS last_swap_time
     FROM pool_stats ps
@@ -249,6 +672,7 @@ BEGIN
         AND c.interval_seconds = 3600
         AND c.timestamp > EXTRACT(EPOCH FROM NOW())::BIGINT - 86400
     WHERE ps.pool_address = p_pool
-    GROUP BY ps.pool_address, ps.swap_count, ps.total_volume, ps.last_swap_time;
+    GROUP BY ps.pool_address, ps.swap_count, ps.total_volume0, ps.total_volume1,
+        ps.total_volume_usd, ps.last_swap_time;
 END;
 $$ LANGUAGE plpgsql;
@@ -1,229 +1,171 @@
-// Generated ABI module for Aerodrome Pool events
-pub mod pool {
-    pub mod events {
-        use substreams_ethereum::Event;
-
-        #[derive(Debug, Clone, PartialEq)]
-        pub struct Swap {
-            pub sender: Vec<u8>,
-            pub to: Vec<u8>,
-            pub amount0_in: substreams::scalar::BigInt,
-            pub amount1_in: substreams::scalar::BigInt,
-            pub amount0_out: substreams::scalar::BigInt,
-            pub amount1_out: substreams::scalar::BigInt,
-        }
+// Contract bindings generated by build.rs from every ABI JSON in `abi/`
+include!(concat!(env!("OUT_DIR"), "/abi/mod.rs"));
+
+#[cfg(test)]
+mod tests {
+    use super::pool::events::{Burn, Claim, Fees, Mint, Swap, Sync};
+    use hex_literal::hex;
+    use substreams::scalar::BigInt;
+    use substreams_ethereum::pb::eth::v2::Log;
+    use substreams_ethereum::Event;
+
+    // vAMM-WETH/USDC pool and the Aerodrome Router on Base
+    const POOL: [u8; 20] = hex!("cdac0d6c6c59727a65f871236188350531885c43");
+    const ROUTER: [u8; 20] = hex!("cf77a3ba9a5ca399b7c97c74d54e5b1beb874e43");
+    const TRADER: [u8; 20] = hex!("9cc9e01ed66b3c5ffbeb33029b6b08663b6de88b");
+
+    fn address_topic(address: [u8; 20]) -> Vec<u8> {
+        let mut topic = vec![0u8; 12];
+        topic.extend_from_slice(&address);
+        topic
+    }
 
-        impl Event for Swap {
-            const NAME: &'static str = "Swap";
-
-            fn match_log(log: &substreams_ethereum::pb::eth::v2::Log) -> bool {
-                if log.topics.len() != 3 {
-                    return false;
-                }
-                // Swap(address indexed sender, address indexed to, uint256 amount0In, uint256 amount1In, uint256 amount0Out, uint256 amount1Out)
-                // keccak256("Swap(address,address,uint256,uint256,uint256,uint256)")
-                log.topics[0].as_slice() == &[
-                    0xd7, 0x8a, 0xd9, 0x5f, 0xa4, 0x6c, 0x99, 0x4b,
-                    0x6e, 0x1f, 0x56, 0x21, 0x3a, 0x60, 0x3c, 0xc6,
-                    0x6e, 0x23, 0x23, 0xba, 0x6f, 0x7b, 0x2d, 0x7f,
-                    0xbb, 0x7f, 0xed, 0x15, 0x75, 0x49, 0xdf, 0x08,
-                ]
-            }
-
-            fn decode(log: &substreams_ethereum::pb::eth::v2::Log) -> Result<Self, String> {
-                if !Self::match_log(log) {
-                    return Err("Log does not match Swap event".to_string());
-                }
-
-                let sender = log.topics.get(1)
-                    .ok_or("Missing topic 1")?
-                    .get(12..32)
-                    .ok_or("Invalid topic 1 length")?
-                    .to_vec();
-                let to = log.topics.get(2)
-                    .ok_or("Missing topic 2")?
-                    .get(12..32)
-                    .ok_or("Invalid topic 2 length")?
-                    .to_vec();
-
-                let data = &log.data;
-                if data.len() < 128 {
-                    return Err("Data too short for Swap event".to_string());
-                }
-
-                let amount0_in = substreams::scalar::BigInt::from_unsigned_bytes_be(&data[0..32]);
-                let amount1_in = substreams::scalar::BigInt::from_unsigned_bytes_be(&data[32..64]);
-                let amount0_out = substreams::scalar::BigInt::from_unsigned_bytes_be(&data[64..96]);
-                let amount1_out = substreams::scalar::BigInt::from_unsigned_bytes_be(&data[96..128]);
-
-                Ok(Swap {
-                    sender,
-                    to,
-                    amount0_in,
-                    amount1_in,
-                    amount0_out,
-                    amount1_out,
-                })
-            }
-        }
+    fn word(value: u128) -> Vec<u8> {
+        let mut word = vec![0u8; 16];
+        word.extend_from_slice(&value.to_be_bytes());
+        word
+    }
 
-        #[derive(Debug, Clone, PartialEq)]
-        pub struct Mint {
-            pub sender: Vec<u8>,
-            pub to: Vec<u8>,
-            pub amount0: substreams::scalar::BigInt,
-            pub amount1: substreams::scalar::BigInt,
+    fn log(topics: Vec<Vec<u8>>, words: &[u128]) -> Log {
+        Log {
+            address: POOL.to_vec(),
+            topics,
+            data: words.iter().flat_map(|w| word(*w)).collect(),
+            ..Default::default()
         }
+    }
 
-        impl Event for Mint {
-            const NAME: &'static str = "Mint";
-
-            fn match_log(log: &substreams_ethereum::pb::eth::v2::Log) -> bool {
-                if log.topics.len() != 3 {
-                    return false;
-                }
-                // Mint(address indexed sender, uint256 amount0, uint256 amount1, address indexed to)
-                // keccak256("Mint(address,uint256,uint256,address)")
-                log.topics[0].as_slice() == &[
-                    0x4c, 0x20, 0x9b, 0x5f, 0xc8, 0xad, 0x50, 0x75,
-                    0x8f, 0x13, 0xe2, 0xe1, 0x08, 0x8b, 0xa5, 0x6a,
-                    0x56, 0x0d, 0xfe, 0x89, 0xc3, 0x0b, 0xa0, 0x8b,
-                    0x7c, 0x57, 0x4a, 0x69, 0x63, 0x54, 0xae, 0x36,
-                ]
-            }
-
-            fn decode(log: &substreams_ethereum::pb::eth::v2::Log) -> Result<Self, String> {
-                if !Self::match_log(log) {
-                    return Err("Log does not match Mint event".to_string());
-                }
-
-                let sender = log.topics.get(1)
-                    .ok_or("Missing topic 1")?
-                    .get(12..32)
-                    .ok_or("Invalid topic 1 length")?
-                    .to_vec();
-                let to = log.topics.get(2)
-                    .ok_or("Missing topic 2")?
-                    .get(12..32)
-                    .ok_or("Invalid topic 2 length")?
-                    .to_vec();
-
-                let data = &log.data;
-                if data.len() < 64 {
-                    return Err("Data too short for Mint event".to_string());
-                }
-
-                let amount0 = substreams::scalar::BigInt::from_unsigned_bytes_be(&data[0..32]);
-                let amount1 = substreams::scalar::BigInt::from_unsigned_bytes_be(&data[32..64]);
-
-                Ok(Mint {
-                    sender,
-                    to,
-                    amount0,
-                    amount1,
-                })
-            }
-        }
+    #[test]
+    fn test_decode_swap() {
+        let log = log(
+            vec![
+                hex!("b3e2773606abfd36b5bd91394b3a54d1398336c65005baf7bf7a05efeffaf75b").to_vec(),
+                address_topic(ROUTER),
+                address_topic(TRADER),
+            ],
+            &[1_000_000_000_000_000_000, 0, 0, 3_021_456_789],
+        );
+
+        let swap = Swap::match_and_decode(&log).expect("swap should decode");
+        assert_eq!(swap.sender, ROUTER.to_vec());
+        assert_eq!(swap.to, TRADER.to_vec());
+        assert_eq!(swap.amount0_in, BigInt::from(1_000_000_000_000_000_000u64));
+        assert_eq!(swap.amount1_in, BigInt::from(0u64));
+        assert_eq!(swap.amount0_out, BigInt::from(0u64));
+        assert_eq!(swap.amount1_out, BigInt::from(3_021_456_789u64));
+    }
 
-        #[derive(Debug, Clone, PartialEq)]
-        pub struct Burn {
-            pub sender: Vec<u8>,
-            pub to: Vec<u8>,
-            pub amount0: substreams::scalar::BigInt,
-            pub amount1: substreams::scalar::BigInt,
-        }
+    #[test]
+    fn test_decode_mint() {
+        let log = log(
+            vec![
+                hex!("4c209b5fc8ad50758f13e2e1088ba56a560dff690a1c6fef26394f4c03821c4f").to_vec(),
+                address_topic(ROUTER),
+            ],
+            &[500_000_000_000_000_000, 1_510_000_000],
+        );
+
+        let mint = Mint::match_and_decode(&log).expect("mint should decode");
+        assert_eq!(mint.sender, ROUTER.to_vec());
+        assert_eq!(mint.amount0, BigInt::from(500_000_000_000_000_000u64));
+        assert_eq!(mint.amount1, BigInt::from(1_510_000_000u64));
+    }
 
-        impl Event for Burn {
-            const NAME: &'static str = "Burn";
-
-            fn match_log(log: &substreams_ethereum::pb::eth::v2::Log) -> bool {
-                if log.topics.len() != 3 {
-                    return false;
-                }
-                // Burn(address indexed sender, uint256 amount0, uint256 amount1, address indexed to)
-                // keccak256("Burn(address,uint256,uint256,address)")
-                log.topics[0].as_slice() == &[
-                    0xdc, 0xcd, 0x41, 0x2f, 0x0b, 0x12, 0x52, 0x81,
-                    0x90, 0xce, 0x99, 0x95, 0xb5, 0x30, 0x9c, 0x21,
-                    0x02, 0x29, 0x02, 0xf9, 0x3a, 0x30, 0x11, 0x56,
-                    0x9d, 0x3f, 0x53, 0xf3, 0x74, 0x32, 0x00, 0xef,
-                ]
-            }
-
-            fn decode(log: &substreams_ethereum::pb::eth::v2::Log) -> Result<Self, String> {
-                if !Self::match_log(log) {
-                    return Err("Log does not match Burn event".to_string());
-                }
-
-                let sender = log.topics.get(1)
-                    .ok_or("Missing topic 1")?
-                    .get(12..32)
-                    .ok_or("Invalid topic 1 length")?
-                    .to_vec();
-                let to = log.topics.get(2)
-                    .ok_or("Missing topic 2")?
-                    .get(12..32)
-                    .ok_or("Invalid topic 2 length")?
-                    .to_vec();
-
-                let data = &log.data;
-                if data.len() < 64 {
-                    return Err("Data too short for Burn event".to_string());
-                }
-
-                let amount0 = substreams::scalar::BigInt::from_unsigned_bytes_be(&data[0..32]);
-                let amount1 = substreams::scalar::BigInt::from_unsigned_bytes_be(&data[32..64]);
-
-                Ok(Burn {
-                    sender,
-                    to,
-                    amount0,
-                    amount1,
-                })
-            }
-        }
+    #[test]
+    fn test_decode_burn() {
+        let log = log(
+            vec![
+                hex!("5d624aa9c148153ab3446c1b154f660ee7701e549fe9b62dab7171b1c80e6fa2").to_vec(),
+                address_topic(ROUTER),
+                address_topic(TRADER),
+            ],
+            &[250_000_000_000_000_000, 755_000_000],
+        );
+
+        let burn = Burn::match_and_decode(&log).expect("burn should decode");
+        assert_eq!(burn.sender, ROUTER.to_vec());
+        assert_eq!(burn.to, TRADER.to_vec());
+        assert_eq!(burn.amount0, BigInt::from(250_000_000_000_000_000u64));
+        assert_eq!(burn.amount1, BigInt::from(755_000_000u64));
+    }
 
-        #[derive(Debug, Clone, PartialEq)]
-        pub struct Sync {
-            pub reserve0: substreams::scalar::BigInt,
-            pub reserve1: substreams::scalar::BigInt,
-        }
+    #[test]
+    fn test_decode_sync() {
+        // Reserves above uint112 range are still decoded as uint256
+        let reserve0 = u128::MAX;
+        let log = log(
+            vec![hex!("cf2aa50876cdfbb541206f89af0ee78d44a2abf8d328e37fa4917f982149848a").to_vec()],
+            &[reserve0, 4_321_000_000_000],
+        );
+
+        let sync = Sync::match_and_decode(&log).expect("sync should decode");
+        assert_eq!(sync.reserve0.to_string(), reserve0.to_string());
+        assert_eq!(sync.reserve1, BigInt::from(4_321_000_000_000u64));
+    }
 
-        impl Event for Sync {
-            const NAME: &'static str = "Sync";
-
-            fn match_log(log: &substreams_ethereum::pb::eth::v2::Log) -> bool {
-                if log.topics.len() != 1 {
-                    return false;
-                }
-                // Sync(uint112 reserve0, uint112 reserve1)
-                // keccak256("Sync(uint112,uint112)")
-                log.topics[0].as_slice() == &[
-                    0x1c, 0x41, 0x1e, 0x9a, 0x96, 0xe0, 0x71, 0x24,
-                    0x1c, 0x2f, 0x21, 0xf7, 0x72, 0x6b, 0x17, 0xae,
-                    0x89, 0xe3, 0xca, 0xb4, 0xc7, 0x8b, 0xe5, 0x05,
-                    0x09, 0xa0, 0xfa, 0x21, 0x12, 0x56, 0x61, 0x17,
-                ]
-            }
-
-            fn decode(log: &substreams_ethereum::pb::eth::v2::Log) -> Result<Self, String> {
-                if !Self::match_log(log) {
-                    return Err("Log does not match Sync event".to_string());
-                }
-
-                let data = &log.data;
-                if data.len() < 64 {
-                    return Err("Data too short for Sync event".to_string());
-                }
-
-                let reserve0 = substreams::scalar::BigInt::from_unsigned_bytes_be(&data[0..32]);
-                let reserve1 = substreams::scalar::BigInt::from_unsigned_bytes_be(&data[32..64]);
-
-                Ok(Sync {
-                    reserve0,
-                    reserve1,
-                })
-            }
-        }
+    #[test]
+    fn test_decode_fees() {
+        let log = log(
+            vec![
+                hex!("112c256902bf554b6ed882d2936687aaeb4225e8cd5b51303c90ca6cf43a8602").to_vec(),
+                address_topic(ROUTER),
+            ],
+            &[3_000_000_000_000_000, 0],
+        );
+
+        let fees = Fees::match_and_decode(&log).expect("fees should decode");
+        assert_eq!(fees.sender, ROUTER.to_vec());
+        assert_eq!(fees.amount0, BigInt::from(3_000_000_000_000_000u64));
+        assert_eq!(fees.amount1, BigInt::from(0u64));
+    }
+
+    #[test]
+    fn test_decode_claim() {
+        let log = log(
+            vec![
+                hex!("865ca08d59f5cb456e85cd2f7ef63664ea4f73327414e9d8152c4158b0e94645").to_vec(),
+                address_topic(TRADER),
+                address_topic(TRADER),
+            ],
+            &[12_000_000_000_000, 36_000],
+        );
+
+        let claim = Claim::match_and_decode(&log).expect("claim should decode");
+        assert_eq!(claim.sender, TRADER.to_vec());
+        assert_eq!(claim.recipient, TRADER.to_vec());
+        assert_eq!(claim.amount0, BigInt::from(12_000_000_000_000u64));
+        assert_eq!(claim.amount1, BigInt::from(36_000u64));
+    }
+
+    #[test]
+    fn test_uniswap_v2_shapes_do_not_match() {
+        // UniswapV2 Mint(address indexed sender, uint256, uint256) with indexed `to`
+        let v2_mint = log(
+            vec![
+                hex!("4c209b5fc8ad50758f13e2e1088ba56a560dff690a1c6fef26394f4c03821c4f").to_vec(),
+                address_topic(ROUTER),
+                address_topic(TRADER),
+            ],
+            &[1, 1],
+        );
+        assert!(Mint::match_and_decode(&v2_mint).is_none());
+
+        // UniswapV2 Sync(uint112, uint112)
+        let v2_sync = log(
+            vec![hex!("1c411e9a96e071241c2f21f7726b17ae89e3cab4c78be50e062b03a9fffbbad1").to_vec()],
+            &[1, 1],
+        );
+        assert!(Sync::match_and_decode(&v2_sync).is_none());
+
+        // UniswapV2 Swap(address indexed sender, uint256, uint256, uint256, uint256, address indexed to)
+        let v2_swap = log(
+            vec![
+                hex!("d78ad95fa46c994b6551d0da85fc275fe613ce37657fb8d5e3d130840159d822").to_vec(),
+                address_topic(ROUTER),
+                address_topic(TRADER),
+            ],
+            &[1, 0, 0, 1],
+        );
+        assert!(Swap::match_and_decode(&v2_swap).is_none());
     }
 }
@@ -0,0 +1,145 @@
+//! Calendar buckets for time-series keys
+//!
+//! Provides the buckets stores aggregate by:
+//! - Day (`YYYY-MM-DD`) and hour (`YYYY-MM-DD-HH`)
+//! - Aerodrome epoch, the voting week flipping Thursday 00:00 UTC
+//! - ISO week (`YYYY-Www`) and calendar month (`YYYY-MM`)
+
+/// Seconds in a day
+const DAY: u64 = 86_400;
+
+/// Length of an Aerodrome epoch in seconds
+pub(crate) const WEEK: u64 = 7 * DAY;
+
+/// Aerodrome epoch of a Unix timestamp
+///
+/// Epochs are weeks flipping Thursday 00:00 UTC, so they are counted from
+/// the Unix epoch (itself a Thursday) like the Voter's `epochStart`.
+pub(crate) fn epoch_number(timestamp: u64) -> u64 {
+    timestamp / WEEK
+}
+
+/// Unix timestamp at which an epoch starts
+pub(crate) fn epoch_start(epoch: u64) -> u64 {
+    epoch * WEEK
+}
+
+/// Format Unix timestamp to YYYY-MM-DD date string
+pub(crate) fn format_date(timestamp: u64) -> String {
+    let (year, month, day, _) = civil_date(timestamp / DAY);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+/// Format Unix timestamp to YYYY-MM-DD-HH hour string
+pub(crate) fn format_hour(timestamp: u64) -> String {
+    let date = format_date(timestamp);
+    let hour = (timestamp % DAY) / 3600;
+    format!("{}-{:02}", date, hour)
+}
+
+/// Format Unix timestamp to YYYY-MM month string
+pub(crate) fn format_month(timestamp: u64) -> String {
+    let (year, month, _, _) = civil_date(timestamp / DAY);
+    format!("{:04}-{:02}", year, month)
+}
+
+/// Format Unix timestamp to YYYY-Www ISO 8601 week string
+///
+/// ISO weeks start on Monday and belong to the year of their Thursday, so
+/// the first days of January can fall in the previous year's last week.
+pub(crate) fn format_iso_week(timestamp: u64) -> String {
+    let days = timestamp / DAY;
+    // 1970-01-01 was a Thursday: Monday = 0
+    let weekday = (days + 3) % 7;
+    let thursday = days + 3 - weekday;
+    let (year, _, _, day_of_year) = civil_date(thursday);
+    format!("{:04}-W{:02}", year, day_of_year / 7 + 1)
+}
+
+/// Year, month, day and zero-based day of year of a day count since 1970-01-01
+fn civil_date(days: u64) -> (u64, u64, u64, u64) {
+    let mut year = 1970u64;
+    let mut remaining_days = days;
+
+    loop {
+        let days_in_year = if is_leap_year(year) { 366 } else { 365 };
+        if remaining_days < days_in_year {
+            break;
+        }
+        remaining_days -= days_in_year;
+        year += 1;
+    }
+    let day_of_year = remaining_days;
+
+    let days_in_months: [u64; 12] = if is_leap_year(year) {
+        [31, 29, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31]
+    } else {
+        [31, 28, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31]
+    };
+
+    let mut month = 1u64;
+    for days_in_month in days_in_months.iter() {
+        if remaining_days < *days_in_month {
+            break;
+        }
+        remaining_days -= days_in_month;
+        month += 1;
+    }
+
+    (year, month, remaining_days + 1, day_of_year)
+}
+
+/// Check if a year is a leap year
+#[inline]
+fn is_leap_year(year: u64) -> bool {
+    (year.is_multiple_of(4) && !year.is_multiple_of(100)) || year.is_multiple_of(400)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_date() {
+        assert_eq!(format_date(0), "1970-01-01");
+        assert_eq!(format_date(1705276800), "2024-01-15");
+        assert_eq!(format_date(1582934400), "2020-02-29"); // Leap year
+    }
+
+    #[test]
+    fn test_format_hour() {
+        assert_eq!(format_hour(0), "1970-01-01-00");
+        assert_eq!(format_hour(3600), "1970-01-01-01");
+        assert_eq!(format_hour(86399), "1970-01-01-23");
+    }
+
+    #[test]
+    fn test_is_leap_year() {
+        assert!(is_leap_year(2020));
+        assert!(is_leap_year(2000));
+        assert!(!is_leap_year(2100));
+        assert!(!is_leap_year(2023));
+    }
+
+    #[test]
+    fn test_epoch_number() {
+        // 2024-01-04 00:00 UTC (Thursday) starts a new epoch
+        assert_eq!(epoch_number(1_704_326_399) + 1, epoch_number(1_704_326_400));
+        assert_eq!(epoch_number(1_704_326_400), epoch_number(1_704_931_199));
+        assert_eq!(epoch_start(epoch_number(1_704_931_199)), 1_704_326_400);
+    }
+
+    #[test]
+    fn test_format_month() {
+        assert_eq!(format_month(1705276800), "2024-01");
+        assert_eq!(format_month(1582934400), "2020-02");
+    }
+
+    #[test]
+    fn test_format_iso_week() {
+        assert_eq!(format_iso_week(1705276800), "2024-W03"); // Monday 2024-01-15
+        assert_eq!(format_iso_week(1704067200), "2024-W01"); // Monday 2024-01-01
+        assert_eq!(format_iso_week(1704067199), "2023-W52"); // Sunday 2023-12-31
+        assert_eq!(format_iso_week(1609459200), "2020-W53"); // Friday 2021-01-01
+    }
+}
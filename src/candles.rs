@@ -0,0 +1,303 @@
+//! OHLCV candle modules
+//!
+//! Candles are built from the decimal-adjusted execution price of token0 in
+//! token1 (`price0`) of V2-style and Slipstream swaps at 5m, 1h, 4h and 1d
+//! intervals. Each OHLC component lives in a store whose update policy
+//! computes it (first, max, min, last) and every key is
+//! `{pool}:{interval}:{bucket}`, where `bucket` is the Unix timestamp at
+//! which the candle opens.
+
+use crate::pb::aerodrome::{Candle, Candles, ClSwapEvents, SwapEvents};
+use std::collections::{BTreeMap, BTreeSet};
+use substreams::scalar::BigDecimal;
+use substreams::store::{
+    StoreAdd, StoreAddBigDecimal, StoreGet, StoreGetBigDecimal, StoreGetInt64, StoreMax,
+    StoreMaxBigDecimal, StoreMin, StoreMinBigDecimal, StoreNew, StoreSet, StoreSetBigDecimal,
+    StoreSetIfNotExists, StoreSetIfNotExistsBigDecimal, StoreSetInt64,
+};
+
+/// Candle intervals in seconds: 5m, 1h, 4h, 1d
+pub const INTERVALS: [u64; 4] = [300, 3600, 14400, 86400];
+
+/// Start of the candle containing `timestamp`
+pub fn bucket_start(timestamp: u64, interval: u64) -> u64 {
+    timestamp - timestamp % interval
+}
+
+/// Store key of a candle
+pub fn candle_key(pool: &str, interval: u64, bucket: u64) -> String {
+    format!("{}:{}:{}", pool, interval, bucket)
+}
+
+/// Candle keys touched by a swap, one per interval
+fn swap_candle_keys(pool: &str, timestamp: u64) -> impl Iterator<Item = String> + '_ {
+    INTERVALS
+        .iter()
+        .map(move |interval| candle_key(pool, *interval, bucket_start(timestamp, *interval)))
+}
+
+/// Parse a decimal proto string, `None` when it is empty (e.g. undefined price)
+fn parse_decimal(value: &str) -> Option<BigDecimal> {
+    value.parse().ok()
+}
+
+/// A V2-style or Slipstream swap as seen by the candles
+struct Trade<'a> {
+    ordinal: u64,
+    pool_address: &'a str,
+    timestamp: u64,
+    price0: Option<BigDecimal>,
+    /// Token0 traded (decimal-adjusted)
+    volume0: BigDecimal,
+    /// Token1 traded (decimal-adjusted)
+    volume1: BigDecimal,
+}
+
+/// Swaps of both pool types in block order (log ordinal), so the last write
+/// of a block wins
+fn trades<'a>(swaps: &'a SwapEvents, cl_swaps: &'a ClSwapEvents) -> Vec<Trade<'a>> {
+    let amount = |value: &str| parse_decimal(value).unwrap_or_else(BigDecimal::zero);
+
+    let v2 = swaps.swaps.iter().map(|swap| Trade {
+        ordinal: swap.ordinal,
+        pool_address: &swap.pool_address,
+        timestamp: swap.timestamp,
+        price0: parse_decimal(&swap.price0),
+        volume0: amount(&swap.amount0_in_decimal) + amount(&swap.amount0_out_decimal),
+        volume1: amount(&swap.amount1_in_decimal) + amount(&swap.amount1_out_decimal),
+    });
+    let cl = cl_swaps.swaps.iter().map(|swap| Trade {
+        ordinal: swap.ordinal,
+        pool_address: &swap.pool_address,
+        timestamp: swap.timestamp,
+        price0: parse_decimal(&swap.price0),
+        volume0: amount(&swap.amount0_decimal).absolute(),
+        volume1: amount(&swap.amount1_decimal).absolute(),
+    });
+
+    let mut trades: Vec<Trade> = v2.chain(cl).collect();
+    trades.sort_by_key(|trade| trade.ordinal);
+    trades
+}
+
+/// Store handler for candle open prices (first price of the bucket)
+#[substreams::handlers::store]
+pub fn store_candles_open(
+    swaps: SwapEvents,
+    cl_swaps: ClSwapEvents,
+    store: StoreSetIfNotExistsBigDecimal,
+) {
+    for trade in trades(&swaps, &cl_swaps) {
+        let Some(price) = trade.price0 else {
+            continue;
+        };
+        for key in swap_candle_keys(trade.pool_address, trade.timestamp) {
+            store.set_if_not_exists(0, key, &price);
+        }
+    }
+}
+
+/// Store handler for candle high prices
+#[substreams::handlers::store]
+pub fn store_candles_high(swaps: SwapEvents, cl_swaps: ClSwapEvents, store: StoreMaxBigDecimal) {
+    for trade in trades(&swaps, &cl_swaps) {
+        let Some(price) = trade.price0 else {
+            continue;
+        };
+        for key in swap_candle_keys(trade.pool_address, trade.timestamp) {
+            store.max(0, key, &price);
+        }
+    }
+}
+
+/// Store handler for candle low prices
+#[substreams::handlers::store]
+pub fn store_candles_low(swaps: SwapEvents, cl_swaps: ClSwapEvents, store: StoreMinBigDecimal) {
+    for trade in trades(&swaps, &cl_swaps) {
+        let Some(price) = trade.price0 else {
+            continue;
+        };
+        for key in swap_candle_keys(trade.pool_address, trade.timestamp) {
+            store.min(0, key, &price);
+        }
+    }
+}
+
+/// Store handler for candle close prices (last price of the bucket)
+///
+/// Trades are in block order, so the last write of the block wins.
+#[substreams::handlers::store]
+pub fn store_candles_close(swaps: SwapEvents, cl_swaps: ClSwapEvents, store: StoreSetBigDecimal) {
+    for trade in trades(&swaps, &cl_swaps) {
+        let Some(price) = trade.price0 else {
+            continue;
+        };
+        for key in swap_candle_keys(trade.pool_address, trade.timestamp) {
+            store.set(0, key, &price);
+        }
+    }
+}
+
+/// Store handler for candle volumes and trade counts
+///
+/// Key formats:
+/// - `{pool}:{interval}:{bucket}:volume0` - Token0 traded (decimal-adjusted)
+/// - `{pool}:{interval}:{bucket}:volume1` - Token1 traded (decimal-adjusted)
+/// - `{pool}:{interval}:{bucket}:trades` - Number of swaps
+#[substreams::handlers::store]
+pub fn store_candles_volume(swaps: SwapEvents, cl_swaps: ClSwapEvents, store: StoreAddBigDecimal) {
+    for trade in trades(&swaps, &cl_swaps) {
+        for key in swap_candle_keys(trade.pool_address, trade.timestamp) {
+            store.add(0, format!("{}:volume0", key), &trade.volume0);
+            store.add(0, format!("{}:volume1", key), &trade.volume1);
+            store.add(0, format!("{}:trades", key), BigDecimal::one());
+        }
+    }
+}
+
+/// Store handler for the latest candle bucket of each pool and interval
+///
+/// Key formats:
+/// - `{pool}:{interval}` - Bucket of the most recent candle
+#[substreams::handlers::store]
+pub fn store_candles_latest(swaps: SwapEvents, cl_swaps: ClSwapEvents, store: StoreSetInt64) {
+    for trade in trades(&swaps, &cl_swaps) {
+        for interval in INTERVALS {
+            let key = format!("{}:{}", trade.pool_address, interval);
+            store.set(0, key, &(bucket_start(trade.timestamp, interval) as i64));
+        }
+    }
+}
+
+/// Emit every candle updated in this block and every candle it completed
+///
+/// A candle is completed once a later candle of the same pool and interval
+/// opens; the block that opens it emits the previous one with `closed` set.
+#[substreams::handlers::map]
+pub fn map_candles(
+    swaps: SwapEvents,
+    cl_swaps: ClSwapEvents,
+    open: StoreGetBigDecimal,
+    high: StoreGetBigDecimal,
+    low: StoreGetBigDecimal,
+    close: StoreGetBigDecimal,
+    volume: StoreGetBigDecimal,
+    latest: StoreGetInt64,
+) -> Result<Candles, substreams::errors::Error> {
+    // Buckets touched by this block, per pool and interval
+    let mut buckets: BTreeMap<(&str, u64), BTreeSet<u64>> = BTreeMap::new();
+    for trade in trades(&swaps, &cl_swaps) {
+        for interval in INTERVALS {
+            buckets
+                .entry((trade.pool_address, interval))
+                .or_default()
+                .insert(bucket_start(trade.timestamp, interval));
+        }
+    }
+
+    let mut candles = Vec::new();
+    for ((pool, interval), mut touched) in buckets {
+        // The candle open before this block, if it is not the one being updated
+        if let Some(previous) = latest.get_first(format!("{}:{}", pool, interval)) {
+            touched.insert(previous as u64);
+        }
+        let current = *touched.last().expect("at least one bucket per entry");
+
+        for bucket in touched {
+            let key = candle_key(pool, interval, bucket);
+            let component = |store: &StoreGetBigDecimal, key: &str| {
+                store
+                    .get_last(key)
+                    .map(|v| v.to_string())
+                    .unwrap_or_default()
+            };
+
+            candles.push(Candle {
+                pool_address: pool.to_string(),
+                interval_seconds: interval,
+                timestamp: bucket,
+                open: component(&open, &key),
+                high: component(&high, &key),
+                low: component(&low, &key),
+                close: component(&close, &key),
+                volume0: component(&volume, &format!("{}:volume0", key)),
+                volume1: component(&volume, &format!("{}:volume1", key)),
+                trade_count: volume
+                    .get_last(format!("{}:trades", key))
+                    .map(|v| v.to_bigint().to_u64())
+                    .unwrap_or(0),
+                closed: bucket != current,
+            });
+        }
+    }
+
+    Ok(Candles { candles })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pb::aerodrome::{AerodromeClSwap, AerodromeSwap};
+
+    #[test]
+    fn test_trades_block_order() {
+        // Two transactions: log indexes restart at 0 in each, so only the
+        // block-wide ordinal gives their order
+        let swaps = SwapEvents {
+            swaps: vec![AerodromeSwap {
+                log_index: 1,
+                ordinal: 40,
+                price0: "2500".to_string(),
+                amount0_in_decimal: "1".to_string(),
+                amount1_out_decimal: "2500".to_string(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let cl_swaps = ClSwapEvents {
+            swaps: vec![AerodromeClSwap {
+                log_index: 5,
+                ordinal: 12,
+                price0: "2490".to_string(),
+                amount0_decimal: "-2".to_string(),
+                amount1_decimal: "4980".to_string(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let trades = trades(&swaps, &cl_swaps);
+        assert_eq!(trades.len(), 2);
+        // The Slipstream swap of the earlier transaction comes first despite
+        // its higher log index, with unsigned volumes
+        assert_eq!(trades[0].ordinal, 12);
+        assert_eq!(trades[0].volume0, BigDecimal::from(2));
+        assert_eq!(trades[0].volume1, BigDecimal::from(4980));
+        assert_eq!(trades[1].price0, Some(BigDecimal::from(2500)));
+        assert_eq!(trades[1].volume1, BigDecimal::from(2500));
+    }
+
+    #[test]
+    fn test_bucket_start() {
+        // 2024-01-01 01:07:30 UTC
+        let timestamp = 1704071250;
+        assert_eq!(bucket_start(timestamp, 300), 1704071100);
+        assert_eq!(bucket_start(timestamp, 3600), 1704070800);
+        assert_eq!(bucket_start(timestamp, 14400), 1704067200);
+        assert_eq!(bucket_start(timestamp, 86400), 1704067200);
+    }
+
+    #[test]
+    fn test_swap_candle_keys() {
+        let keys: Vec<String> = swap_candle_keys("cdac0d6c", 1704071250).collect();
+        assert_eq!(
+            keys,
+            vec![
+                "cdac0d6c:300:1704071100",
+                "cdac0d6c:3600:1704070800",
+                "cdac0d6c:14400:1704067200",
+                "cdac0d6c:86400:1704067200",
+            ]
+        );
+    }
+}
@@ -0,0 +1,127 @@
+//! AERO emissions and veAERO rebases
+//!
+//! At the start of every epoch the Minter mints the weekly emissions for
+//! gauges (`Mint`) and sends the veAERO rebase to the RewardsDistributor,
+//! which records it with `CheckpointToken`. Lock owners later `Claim` their
+//! share. Once emissions reach the tail, voters can `Nudge` the tail
+//! emission rate once per epoch.
+
+use crate::abi::minter::events::{Mint, Nudge};
+use crate::abi::rewards_distributor::events::{CheckpointToken, Claimed};
+use crate::calendar::{epoch_number, WEEK};
+use crate::math::parse_bigint;
+use crate::pb::aerodrome::{EmissionEvent, EmissionEvents};
+use hex_literal::hex;
+use substreams::store::{StoreAdd, StoreAddBigInt, StoreNew};
+use substreams::Hex;
+use substreams_ethereum::pb::eth::v2 as eth;
+use substreams_ethereum::Event;
+
+/// Aerodrome Minter on Base
+const MINTER: [u8; 20] = hex!("eb018363f0a9af8f91f06fee6613a751b2a33fe5");
+
+/// Aerodrome RewardsDistributor (veAERO rebases) on Base
+const REWARDS_DISTRIBUTOR: [u8; 20] = hex!("227f65131a261548b057215bb1d5ab2997964c7d");
+
+/// Extract emissions, tail rate changes and rebases
+#[substreams::handlers::map]
+pub fn map_emissions(blk: eth::Block) -> Result<EmissionEvents, substreams::errors::Error> {
+    let timestamp = blk
+        .header
+        .as_ref()
+        .and_then(|h| h.timestamp.as_ref())
+        .map(|t| t.seconds)
+        .unwrap_or(0) as u64;
+
+    let mut events = Vec::new();
+
+    for receipt in blk.receipts() {
+        let tx_hash = Hex(&receipt.transaction.hash).to_string();
+
+        for log in &receipt.receipt.logs {
+            let mut event = EmissionEvent {
+                block_number: blk.number,
+                transaction_hash: tx_hash.clone(),
+                log_index: log.index as u64,
+                epoch: epoch_number(timestamp),
+                timestamp,
+                ..Default::default()
+            };
+
+            if log.address == MINTER {
+                if let Some(mint) = Mint::match_and_decode(log) {
+                    event.action = "mint".to_string();
+                    event.amount = mint.weekly.to_string();
+                    event.circulating_supply = mint.circulating_supply.to_string();
+                    event.tail = mint.tail;
+                } else if let Some(nudge) = Nudge::match_and_decode(log) {
+                    event.action = "nudge".to_string();
+                    // The nudged period is the epoch start timestamp
+                    event.epoch = nudge.period.to_u64() / WEEK;
+                    event.old_rate = nudge.old_rate.to_string();
+                    event.new_rate = nudge.new_rate.to_string();
+                } else {
+                    continue;
+                }
+            } else if log.address == REWARDS_DISTRIBUTOR {
+                if let Some(checkpoint) = CheckpointToken::match_and_decode(log) {
+                    event.action = "checkpoint_token".to_string();
+                    event.amount = checkpoint.tokens.to_string();
+                } else if let Some(claimed) = Claimed::match_and_decode(log) {
+                    event.action = "claimed".to_string();
+                    event.amount = claimed.amount.to_string();
+                    event.token_id = claimed.token_id.to_string();
+                } else {
+                    continue;
+                }
+            } else {
+                continue;
+            }
+
+            events.push(event);
+        }
+    }
+
+    Ok(EmissionEvents { events })
+}
+
+/// Store handler for veAERO rebases per epoch
+///
+/// Key formats:
+/// - `epoch:{n}:rebase` - AERO added to the RewardsDistributor in the epoch
+/// - `epoch:{n}:rebase_claimed` - Rebases claimed by lock owners in the epoch
+#[substreams::handlers::store]
+pub fn store_rebases(events: EmissionEvents, store: StoreAddBigInt) {
+    for event in &events.events {
+        let column = match event.action.as_str() {
+            "checkpoint_token" => "rebase",
+            "claimed" => "rebase_claimed",
+            _ => continue,
+        };
+        store.add(
+            0,
+            format!("epoch:{}:{}", event.epoch, column),
+            &parse_bigint(&event.amount),
+        );
+    }
+}
+
+/// Split an `epoch:{n}:{column}` key of `store_rebases`
+pub(crate) fn parse_rebase_key(key: &str) -> Option<(u64, &str)> {
+    let (epoch, column) = key.strip_prefix("epoch:")?.split_once(':')?;
+    Some((epoch.parse().ok()?, column))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_rebase_key() {
+        assert_eq!(
+            parse_rebase_key("epoch:2818:rebase_claimed"),
+            Some((2818, "rebase_claimed"))
+        );
+        assert_eq!(parse_rebase_key("total:rebase"), None);
+    }
+}
@@ -0,0 +1,377 @@
+//! VotingEscrow (veAERO) locks
+//!
+//! Locks are NFTs. Every lock event is decoded into one `VeLockEvent` per
+//! token it changes: `Merge` empties the source lock into the target,
+//! `Split` empties the source lock into two new ones, and `DepositManaged` /
+//! `WithdrawManaged` move a lock's AERO into and out of a managed lock.
+//!
+//! Lock state is split across stores by update policy and assembled into
+//! `store_ve_locks`:
+//! - `store_ve_lock_amounts` - locked AERO (add)
+//! - `store_ve_lock_ends` - unlock time (set)
+//! - `store_ve_lock_owners` - current NFT owner (set)
+//!
+//! Permanent locks have no unlock time, so a lock is permanent when it holds
+//! AERO and its unlock time is zero.
+
+use crate::abi::voting_escrow::events::{
+    Deposit, DepositManaged, LockPermanent, Merge, Split, Transfer, UnlockPermanent, Withdraw,
+    WithdrawManaged,
+};
+use crate::calendar::WEEK;
+use crate::math::parse_bigint;
+use crate::pb::aerodrome::{VeLock, VeLockEvent, VeLockEvents};
+use hex_literal::hex;
+use std::collections::HashSet;
+use substreams::scalar::BigInt;
+use substreams::store::{
+    StoreAdd, StoreAddBigInt, StoreGet, StoreGetBigInt, StoreGetInt64, StoreGetString, StoreNew,
+    StoreSet, StoreSetInt64, StoreSetProto, StoreSetString,
+};
+use substreams::Hex;
+use substreams_ethereum::pb::eth::v2 as eth;
+use substreams_ethereum::Event;
+
+/// Aerodrome VotingEscrow (veAERO) on Base
+const VOTING_ESCROW: [u8; 20] = hex!("ebf418fe2512e7e6bd9b87a8f0f294acdc67e6b4");
+
+/// Maximum lock duration (4 years)
+const MAXTIME: u64 = 4 * 365 * 86_400;
+
+/// Extract veAERO lock changes from the VotingEscrow
+#[substreams::handlers::map]
+pub fn map_ve_locks(blk: eth::Block) -> Result<VeLockEvents, substreams::errors::Error> {
+    let timestamp = blk
+        .header
+        .as_ref()
+        .and_then(|h| h.timestamp.as_ref())
+        .map(|t| t.seconds)
+        .unwrap_or(0);
+
+    let mut events = Vec::new();
+
+    for receipt in blk.receipts() {
+        let tx_hash = Hex(&receipt.transaction.hash).to_string();
+
+        for log in &receipt.receipt.logs {
+            if log.address != VOTING_ESCROW {
+                continue;
+            }
+
+            let base = VeLockEvent {
+                block_number: blk.number,
+                transaction_hash: tx_hash.clone(),
+                log_index: log.index as u64,
+                timestamp: timestamp as u64,
+                ..Default::default()
+            };
+
+            if let Some(deposit) = Deposit::match_and_decode(log) {
+                events.push(VeLockEvent {
+                    token_id: deposit.token_id.to_string(),
+                    action: "deposit".to_string(),
+                    owner: Hex(&deposit.provider).to_string(),
+                    amount: deposit.value.to_string(),
+                    unlock_time: deposit.locktime.to_u64(),
+                    deposit_type: deposit_type(deposit.deposit_type.to_u64()).to_string(),
+                    ..base
+                });
+            } else if let Some(withdraw) = Withdraw::match_and_decode(log) {
+                events.push(VeLockEvent {
+                    token_id: withdraw.token_id.to_string(),
+                    action: "withdraw".to_string(),
+                    owner: Hex(&withdraw.provider).to_string(),
+                    amount: withdraw.value.neg().to_string(),
+                    ..base
+                });
+            } else if let Some(merge) = Merge::match_and_decode(log) {
+                let sender = Hex(&merge.sender).to_string();
+                events.push(VeLockEvent {
+                    token_id: merge.from.to_string(),
+                    action: "merge".to_string(),
+                    owner: sender.clone(),
+                    amount: merge.amount_from.neg().to_string(),
+                    related_token_id: merge.to.to_string(),
+                    ..base.clone()
+                });
+                events.push(VeLockEvent {
+                    token_id: merge.to.to_string(),
+                    action: "merge".to_string(),
+                    owner: sender,
+                    amount: merge.amount_from.to_string(),
+                    unlock_time: merge.locktime.to_u64(),
+                    related_token_id: merge.from.to_string(),
+                    ..base
+                });
+            } else if let Some(split) = Split::match_and_decode(log) {
+                let sender = Hex(&split.sender).to_string();
+                let unlock_time = split.locktime.to_u64();
+                let total = split.split_amount1.clone() + split.split_amount2.clone();
+                events.push(VeLockEvent {
+                    token_id: split.from.to_string(),
+                    action: "split".to_string(),
+                    owner: sender.clone(),
+                    amount: total.neg().to_string(),
+                    ..base.clone()
+                });
+                for (token_id, amount) in [
+                    (&split.token_id1, &split.split_amount1),
+                    (&split.token_id2, &split.split_amount2),
+                ] {
+                    events.push(VeLockEvent {
+                        token_id: token_id.to_string(),
+                        action: "split".to_string(),
+                        owner: sender.clone(),
+                        amount: amount.to_string(),
+                        unlock_time,
+                        related_token_id: split.from.to_string(),
+                        ..base.clone()
+                    });
+                }
+            } else if let Some(deposit) = DepositManaged::match_and_decode(log) {
+                // The lock is emptied into the managed lock, which is permanent
+                events.extend(managed_events(
+                    &base,
+                    "deposit_managed",
+                    Hex(&deposit.owner).to_string(),
+                    (&deposit.token_id, &deposit.m_token_id),
+                    &deposit.weight,
+                    0,
+                ));
+            } else if let Some(withdraw) = WithdrawManaged::match_and_decode(log) {
+                // The lock gets its AERO and managed rewards back, locked for
+                // the maximum duration
+                events.extend(managed_events(
+                    &base,
+                    "withdraw_managed",
+                    Hex(&withdraw.owner).to_string(),
+                    (&withdraw.m_token_id, &withdraw.token_id),
+                    &withdraw.weight,
+                    unlock_permanent_end(withdraw.ts.to_u64()),
+                ));
+            } else if let Some(lock) = LockPermanent::match_and_decode(log) {
+                events.push(VeLockEvent {
+                    token_id: lock.token_id.to_string(),
+                    action: "lock_permanent".to_string(),
+                    owner: Hex(&lock.owner).to_string(),
+                    amount: "0".to_string(),
+                    ..base
+                });
+            } else if let Some(unlock) = UnlockPermanent::match_and_decode(log) {
+                events.push(VeLockEvent {
+                    token_id: unlock.token_id.to_string(),
+                    action: "unlock_permanent".to_string(),
+                    owner: Hex(&unlock.owner).to_string(),
+                    amount: "0".to_string(),
+                    unlock_time: unlock_permanent_end(unlock.ts.to_u64()),
+                    ..base
+                });
+            } else if let Some(transfer) = Transfer::match_and_decode(log) {
+                let to = Hex(&transfer.to).to_string();
+                events.push(VeLockEvent {
+                    token_id: transfer.token_id.to_string(),
+                    action: "transfer".to_string(),
+                    owner: to.clone(),
+                    amount: "0".to_string(),
+                    from: Hex(&transfer.from).to_string(),
+                    to,
+                    ..base
+                });
+            }
+        }
+    }
+
+    Ok(VeLockEvents { events })
+}
+
+/// Store handler for locked AERO
+///
+/// Key formats:
+/// - `lock:{token_id}:amount` - AERO locked in the lock
+/// - `total:amount` - AERO locked across all locks
+#[substreams::handlers::store]
+pub fn store_ve_lock_amounts(events: VeLockEvents, store: StoreAddBigInt) {
+    for event in &events.events {
+        let amount = parse_bigint(&event.amount);
+        if amount.is_zero() {
+            continue;
+        }
+        store.add(0, format!("lock:{}:amount", event.token_id), &amount);
+        store.add(0, "total:amount", &amount);
+    }
+}
+
+/// Store handler for lock unlock times
+///
+/// Key formats:
+/// - `lock:{token_id}:unlock_time` - Lock end, 0 when permanent or withdrawn
+#[substreams::handlers::store]
+pub fn store_ve_lock_ends(events: VeLockEvents, store: StoreSetInt64) {
+    for event in &events.events {
+        // Transfers move the NFT without touching the lock
+        if event.action == "transfer" {
+            continue;
+        }
+        store.set(
+            0,
+            format!("lock:{}:unlock_time", event.token_id),
+            &(event.unlock_time as i64),
+        );
+    }
+}
+
+/// Store handler for lock NFT owners
+///
+/// Key formats:
+/// - `lock:{token_id}` - Current owner, the zero address once burned
+#[substreams::handlers::store]
+pub fn store_ve_lock_owners(events: VeLockEvents, store: StoreSetString) {
+    for event in &events.events {
+        if event.action == "transfer" {
+            store.set(0, format!("lock:{}", event.token_id), &event.to);
+        }
+    }
+}
+
+/// Store handler assembling the full state of every lock touched in the block
+///
+/// Key formats:
+/// - `lock:{token_id}` - VeLock with owner, amount and unlock time
+#[substreams::handlers::store]
+pub fn store_ve_locks(
+    events: VeLockEvents,
+    amounts: StoreGetBigInt,
+    ends: StoreGetInt64,
+    owners: StoreGetString,
+    store: StoreSetProto<VeLock>,
+) {
+    let mut seen = HashSet::new();
+
+    for event in &events.events {
+        if !seen.insert(event.token_id.as_str()) {
+            continue;
+        }
+        let key = format!("lock:{}", event.token_id);
+        let amount = amounts
+            .get_last(format!("{}:amount", key))
+            .unwrap_or_else(BigInt::zero);
+        let unlock_time = ends
+            .get_last(format!("{}:unlock_time", key))
+            .unwrap_or_default();
+
+        let lock = VeLock {
+            token_id: event.token_id.clone(),
+            owner: owners.get_last(&key).unwrap_or_default(),
+            permanent: unlock_time == 0 && !amount.is_zero(),
+            amount: amount.to_string(),
+            unlock_time: unlock_time as u64,
+        };
+        store.set(0, &key, &lock);
+    }
+}
+
+/// Lock events for `weight` AERO moved between a lock and a managed lock
+///
+/// `locks` is `(from, to)`. The lock emptied ends with no unlock time, the
+/// receiving lock with `unlock_time`.
+fn managed_events(
+    base: &VeLockEvent,
+    action: &str,
+    owner: String,
+    locks: (&BigInt, &BigInt),
+    weight: &BigInt,
+    unlock_time: u64,
+) -> [VeLockEvent; 2] {
+    let (from, to) = locks;
+    [
+        VeLockEvent {
+            token_id: from.to_string(),
+            action: action.to_string(),
+            owner: owner.clone(),
+            amount: weight.neg().to_string(),
+            related_token_id: to.to_string(),
+            ..base.clone()
+        },
+        VeLockEvent {
+            token_id: to.to_string(),
+            action: action.to_string(),
+            owner,
+            amount: weight.to_string(),
+            unlock_time,
+            related_token_id: from.to_string(),
+            ..base.clone()
+        },
+    ]
+}
+
+/// Name of an `IVotingEscrow.DepositType` value
+fn deposit_type(value: u64) -> &'static str {
+    match value {
+        0 => "deposit_for",
+        1 => "create_lock",
+        2 => "increase_amount",
+        3 => "increase_unlock_time",
+        _ => "unknown",
+    }
+}
+
+/// Lock end set by `unlockPermanent`: the maximum duration, rounded down
+/// to a whole week
+fn unlock_permanent_end(timestamp: u64) -> u64 {
+    (timestamp + MAXTIME) / WEEK * WEEK
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deposit_type() {
+        assert_eq!(deposit_type(1), "create_lock");
+        assert_eq!(deposit_type(3), "increase_unlock_time");
+        assert_eq!(deposit_type(9), "unknown");
+    }
+
+    #[test]
+    fn test_managed_events_move_amount() {
+        let (lock, managed) = (BigInt::from(7u64), BigInt::from(12u64));
+        let weight = BigInt::from(1_000u64);
+
+        let [from, to] = managed_events(
+            &VeLockEvent::default(),
+            "deposit_managed",
+            "cf77a3ba".to_string(),
+            (&lock, &managed),
+            &weight,
+            0,
+        );
+        assert_eq!(
+            (from.token_id.as_str(), from.amount.as_str()),
+            ("7", "-1000")
+        );
+        assert_eq!((to.token_id.as_str(), to.amount.as_str()), ("12", "1000"));
+        assert_eq!(to.related_token_id, "7");
+        // Debit and credit cancel out, leaving `total:amount` unchanged
+        assert!((parse_bigint(&from.amount) + parse_bigint(&to.amount)).is_zero());
+
+        let [from, to] = managed_events(
+            &VeLockEvent::default(),
+            "withdraw_managed",
+            "cf77a3ba".to_string(),
+            (&managed, &lock),
+            &weight,
+            1_830_556_800,
+        );
+        assert_eq!((from.token_id.as_str(), from.unlock_time), ("12", 0));
+        assert_eq!((to.token_id.as_str(), to.unlock_time), ("7", 1_830_556_800));
+    }
+
+    #[test]
+    fn test_unlock_permanent_end() {
+        // 2024-01-04 00:00 UTC, a Thursday
+        let end = unlock_permanent_end(1_704_326_400);
+        assert_eq!(end % WEEK, 0);
+        assert!(end <= 1_704_326_400 + MAXTIME);
+        assert!(end > 1_704_326_400 + MAXTIME - WEEK);
+    }
+}
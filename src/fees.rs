@@ -0,0 +1,179 @@
+//! Pool fee rates
+//!
+//! Every Aerodrome pool charges its factory's stable or volatile default fee
+//! unless governance sets a custom fee for it. Custom fees are logged with
+//! `SetCustomFee`, but `setFee` changes the defaults without an event, so
+//! default changes are decoded from successful calls to the PoolFactory.
+//!
+//! The fee in effect is resolved when read: a pool's custom fee if any,
+//! otherwise the current default of its tier. Fees are in basis points.
+
+use crate::abi::pool_factory::events::SetCustomFee;
+use crate::abi::pool_factory::functions::SetFee;
+use crate::pb::aerodrome::{Pool, PoolFeeEvent, PoolFeeEvents, Pools};
+use crate::POOL_FACTORY;
+use substreams::store::{
+    StoreGet, StoreGetInt64, StoreGetProto, StoreNew, StoreSet, StoreSetInt64,
+};
+use substreams::Hex;
+use substreams_ethereum::pb::eth::v2 as eth;
+use substreams_ethereum::{Event, Function};
+
+/// Stable pool fee set by the PoolFactory constructor (0.05%)
+const DEFAULT_STABLE_FEE: u64 = 5;
+
+/// Volatile pool fee set by the PoolFactory constructor (0.3%)
+const DEFAULT_VOLATILE_FEE: u64 = 30;
+
+/// Custom fee marking a pool that charges no fee
+const ZERO_FEE_INDICATOR: u64 = 420;
+
+/// Extract pool creations and fee changes from the PoolFactory
+#[substreams::handlers::map]
+pub fn map_pool_fees(
+    blk: eth::Block,
+    created: Pools,
+    pools: StoreGetProto<Pool>,
+) -> Result<PoolFeeEvents, substreams::errors::Error> {
+    let timestamp = blk
+        .header
+        .as_ref()
+        .and_then(|h| h.timestamp.as_ref())
+        .map(|t| t.seconds)
+        .unwrap_or(0) as u64;
+
+    // New pools charge their tier's default until a custom fee is set
+    let mut events: Vec<PoolFeeEvent> = created
+        .pools
+        .into_iter()
+        .map(|pool| PoolFeeEvent {
+            block_number: pool.block_number,
+            transaction_hash: pool.transaction_hash,
+            log_index: pool.log_index,
+            ordinal: pool.ordinal,
+            action: "pool_created".to_string(),
+            pool_address: pool.address,
+            stable: pool.stable,
+            fee: 0,
+            timestamp,
+        })
+        .collect();
+
+    for receipt in blk.receipts() {
+        let tx_hash = Hex(&receipt.transaction.hash).to_string();
+        let base = PoolFeeEvent {
+            block_number: blk.number,
+            transaction_hash: tx_hash,
+            timestamp,
+            ..Default::default()
+        };
+
+        for log in &receipt.receipt.logs {
+            if log.address != POOL_FACTORY {
+                continue;
+            }
+            if let Some(custom) = SetCustomFee::match_and_decode(log) {
+                let pool_address = Hex(&custom.pool).to_string();
+                let stable = pools
+                    .get_last(format!("pool:{}", pool_address))
+                    .map(|pool| pool.stable)
+                    .unwrap_or_default();
+                events.push(PoolFeeEvent {
+                    log_index: log.index as u64,
+                    ordinal: log.ordinal,
+                    action: "custom_fee".to_string(),
+                    pool_address,
+                    stable,
+                    fee: custom.fee.to_u64(),
+                    ..base.clone()
+                });
+            }
+        }
+
+        for call in &receipt.transaction.calls {
+            if call.address != POOL_FACTORY || call.state_reverted {
+                continue;
+            }
+            if let Some(set_fee) = SetFee::match_and_decode(call) {
+                events.push(PoolFeeEvent {
+                    log_index: call.index as u64,
+                    ordinal: call.end_ordinal,
+                    action: "default_fee".to_string(),
+                    stable: set_fee.stable,
+                    fee: set_fee.fee.to_u64(),
+                    ..base.clone()
+                });
+            }
+        }
+    }
+
+    events.sort_by_key(|event| event.ordinal);
+    Ok(PoolFeeEvents { events })
+}
+
+/// Store handler for pool fee settings
+///
+/// Values are written at the event ordinal so readers can resolve the fee
+/// in effect at any point of the block with [`fee_in_effect`].
+///
+/// Key formats:
+/// - `pool:{address}` - Custom fee, seeded to 0 (none) when the pool is created
+/// - `default:stable` / `default:volatile` - Default fee of each tier
+#[substreams::handlers::store]
+pub fn store_pool_fees(events: PoolFeeEvents, store: StoreSetInt64) {
+    for event in &events.events {
+        let key = match event.action.as_str() {
+            "pool_created" | "custom_fee" => format!("pool:{}", event.pool_address),
+            "default_fee" => default_key(event.stable).to_string(),
+            _ => continue,
+        };
+        store.set(event.ordinal, key, &(event.fee as i64));
+    }
+}
+
+/// LP fee charged by a pool at an ordinal of the block, in basis points
+pub(crate) fn fee_in_effect(fees: &StoreGetInt64, ordinal: u64, pool: &str, stable: bool) -> u64 {
+    let custom = fees
+        .get_at(ordinal, format!("pool:{}", pool))
+        .unwrap_or_default() as u64;
+    let default = fees
+        .get_at(ordinal, default_key(stable))
+        .map(|fee| fee as u64)
+        .unwrap_or(if stable {
+            DEFAULT_STABLE_FEE
+        } else {
+            DEFAULT_VOLATILE_FEE
+        });
+    resolve_fee(custom, default)
+}
+
+/// Store key of a tier's default fee
+fn default_key(stable: bool) -> &'static str {
+    if stable {
+        "default:stable"
+    } else {
+        "default:volatile"
+    }
+}
+
+/// Fee charged given a pool's custom fee and its tier's default, like the
+/// PoolFactory's `getFee`
+fn resolve_fee(custom: u64, default: u64) -> u64 {
+    match custom {
+        ZERO_FEE_INDICATOR => 0,
+        0 => default,
+        fee => fee,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_fee() {
+        assert_eq!(resolve_fee(0, DEFAULT_VOLATILE_FEE), 30);
+        assert_eq!(resolve_fee(100, DEFAULT_VOLATILE_FEE), 100);
+        assert_eq!(resolve_fee(ZERO_FEE_INDICATOR, DEFAULT_STABLE_FEE), 0);
+    }
+}
@@ -0,0 +1,178 @@
+//! Gauge staking and emissions
+//!
+//! Gauges are discovered from the Voter's `GaugeCreated`. LPs stake pool
+//! tokens in them to earn AERO, which the Voter sends every epoch through
+//! `NotifyReward`.
+//!
+//! Slipstream gauges stake position NFTs with a different `Deposit` and
+//! `Withdraw` layout, so only their rewards and claims are decoded.
+
+use crate::abi::gauge::events::{ClaimRewards, Deposit, NotifyReward, Withdraw};
+use crate::calendar::epoch_number;
+use crate::math::parse_bigint;
+use crate::pb::aerodrome::{Gauge, GaugeEvent, GaugeEvents, GovernanceEvents};
+use substreams::store::{
+    StoreAdd, StoreAddBigInt, StoreGet, StoreGetProto, StoreNew, StoreSet, StoreSetProto,
+};
+use substreams::Hex;
+use substreams_ethereum::pb::eth::v2 as eth;
+use substreams_ethereum::Event;
+
+/// Store handler for gauges created by the Voter
+///
+/// Key formats:
+/// - `gauge:{address}` - Gauge with its pool and voting reward contracts
+/// - `reward:{address}` - Gauge owning the FeesVotingReward or BribeVotingReward
+#[substreams::handlers::store]
+pub fn store_gauges(events: GovernanceEvents, store: StoreSetProto<Gauge>) {
+    for event in &events.events {
+        if event.action != "gauge_created" {
+            continue;
+        }
+        let gauge = Gauge {
+            address: event.gauge.clone(),
+            pool_address: event.pool_address.clone(),
+            bribe_voting_reward: event.bribe_voting_reward.clone(),
+            fees_voting_reward: event.fees_voting_reward.clone(),
+            block_number: event.block_number,
+            timestamp: event.timestamp,
+        };
+        store.set(0, format!("gauge:{}", gauge.address), &gauge);
+        store.set(0, format!("reward:{}", gauge.fees_voting_reward), &gauge);
+        store.set(0, format!("reward:{}", gauge.bribe_voting_reward), &gauge);
+    }
+}
+
+/// Extract staking, claim and reward events from known gauges
+#[substreams::handlers::map]
+pub fn map_gauge_events(
+    blk: eth::Block,
+    gauges: StoreGetProto<Gauge>,
+) -> Result<GaugeEvents, substreams::errors::Error> {
+    let timestamp = blk
+        .header
+        .as_ref()
+        .and_then(|h| h.timestamp.as_ref())
+        .map(|t| t.seconds)
+        .unwrap_or(0) as u64;
+
+    let mut events = Vec::new();
+
+    for receipt in blk.receipts() {
+        let tx_hash = Hex(&receipt.transaction.hash).to_string();
+
+        for log in &receipt.receipt.logs {
+            let (action, account, amount) = if let Some(deposit) = Deposit::match_and_decode(log) {
+                ("deposit", deposit.to, deposit.amount)
+            } else if let Some(withdraw) = Withdraw::match_and_decode(log) {
+                ("withdraw", withdraw.from, withdraw.amount)
+            } else if let Some(claim) = ClaimRewards::match_and_decode(log) {
+                ("claim_rewards", claim.from, claim.amount)
+            } else if let Some(notify) = NotifyReward::match_and_decode(log) {
+                ("notify_reward", notify.from, notify.amount)
+            } else {
+                continue;
+            };
+
+            let gauge_address = Hex(&log.address).to_string();
+            let Some(gauge) = gauges.get_last(format!("gauge:{}", gauge_address)) else {
+                continue;
+            };
+
+            events.push(GaugeEvent {
+                block_number: blk.number,
+                transaction_hash: tx_hash.clone(),
+                log_index: log.index as u64,
+                gauge: gauge_address,
+                pool_address: gauge.pool_address,
+                action: action.to_string(),
+                account: Hex(&account).to_string(),
+                amount: amount.to_string(),
+                epoch: epoch_number(timestamp),
+                timestamp,
+            });
+        }
+    }
+
+    Ok(GaugeEvents { events })
+}
+
+/// Store handler for LP tokens staked in gauges
+///
+/// Key formats:
+/// - `gauge:{address}:staked` - LP tokens staked in the gauge
+#[substreams::handlers::store]
+pub fn store_gauge_stakes(events: GaugeEvents, store: StoreAddBigInt) {
+    for event in &events.events {
+        let amount = parse_bigint(&event.amount);
+        let key = format!("gauge:{}:staked", event.gauge);
+        match event.action.as_str() {
+            "deposit" => store.add(0, key, &amount),
+            "withdraw" => store.add(0, key, amount.neg()),
+            _ => {}
+        }
+    }
+}
+
+/// Store handler for AERO emitted to and claimed from gauges
+///
+/// Key formats:
+/// - `gauge:{address}:emitted` - AERO notified to the gauge
+/// - `gauge:{address}:claimed` - AERO claimed by stakers
+/// - `epoch:{n}:gauge:{address}:emitted` - AERO notified to the gauge in the epoch
+#[substreams::handlers::store]
+pub fn store_gauge_emissions(events: GaugeEvents, store: StoreAddBigInt) {
+    for event in &events.events {
+        let amount = parse_bigint(&event.amount);
+        match event.action.as_str() {
+            "notify_reward" => {
+                store.add(0, format!("gauge:{}:emitted", event.gauge), &amount);
+                store.add(
+                    0,
+                    format!("epoch:{}:gauge:{}:emitted", event.epoch, event.gauge),
+                    &amount,
+                );
+            }
+            "claim_rewards" => {
+                store.add(0, format!("gauge:{}:claimed", event.gauge), &amount);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Map a gauge store key to its gauge, epoch (for per-epoch keys) and SQL column
+pub(crate) fn gauge_column(key: &str) -> Option<(&str, Option<u64>, &'static str)> {
+    let parts: Vec<&str> = key.split(':').collect();
+    let (gauge, epoch, field) = match parts.as_slice() {
+        ["gauge", gauge, field] => (*gauge, None, *field),
+        ["epoch", epoch, "gauge", gauge, field] => (*gauge, Some(epoch.parse().ok()?), *field),
+        _ => return None,
+    };
+    let column = match (epoch, field) {
+        (None, "staked") => "staked_supply",
+        (None, "emitted") => "total_emitted",
+        (None, "claimed") => "total_claimed",
+        (Some(_), "emitted") => "emitted",
+        _ => return None,
+    };
+    Some((gauge, epoch, column))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gauge_column() {
+        assert_eq!(
+            gauge_column("gauge:abc:staked"),
+            Some(("abc", None, "staked_supply"))
+        );
+        assert_eq!(
+            gauge_column("epoch:2818:gauge:abc:emitted"),
+            Some(("abc", Some(2818), "emitted"))
+        );
+        assert_eq!(gauge_column("epoch:2818:gauge:abc:claimed"), None);
+    }
+}
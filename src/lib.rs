@@ -6,24 +6,90 @@
 //! - SQL sink support (PostgreSQL/ClickHouse)
 
 mod abi;
+mod calendar;
+mod candles;
+mod emissions;
+mod escrow;
+mod fees;
+mod gauges;
+mod math;
 mod pb;
+mod positions;
+mod prices;
+mod pricing;
+mod rewards;
+mod sink;
+mod slipstream;
+mod snapshots;
 mod stores;
+mod swaps;
+mod ticks;
+mod tokens;
+mod tvl;
+mod votes;
 
-pub use stores::{store_pool_stats, store_swap_volumes, store_unique_traders};
+pub use candles::{
+    map_candles, store_candles_close, store_candles_high, store_candles_latest,
+    store_candles_low, store_candles_open, store_candles_volume,
+};
+pub use emissions::{map_emissions, store_rebases};
+pub use escrow::{
+    map_ve_locks, store_ve_lock_amounts, store_ve_lock_ends, store_ve_lock_owners, store_ve_locks,
+};
+pub use fees::{map_pool_fees, store_pool_fees};
+pub use gauges::{map_gauge_events, store_gauge_emissions, store_gauge_stakes, store_gauges};
+pub use positions::{
+    map_cl_positions, store_cl_position_amounts, store_cl_position_owners,
+    store_cl_position_ranges, store_cl_positions,
+};
+pub use pricing::{store_pricing_pools, store_token_prices};
+pub use rewards::{map_voting_rewards, store_voting_rewards};
+pub use slipstream::{map_cl_pools_created, map_cl_swaps};
+pub use snapshots::{map_pool_snapshots, store_pool_snapshot_latest, store_pool_snapshot_open};
+pub use ticks::{
+    map_cl_liquidity_depth, store_cl_in_range_liquidity, store_cl_liquidity_checkpoints,
+    store_cl_pool_prices, store_cl_ticks,
+};
+pub use tokens::map_new_tokens;
+pub use tvl::{map_pool_tvl, store_pool_tvl, store_protocol_tvl};
+pub use votes::{map_votes, store_votes};
+pub use stores::{
+    store_cl_pools, store_pool_reserves, store_pool_stats, store_pool_tokens, store_pools,
+    store_swap_volumes, store_swap_volumes_usd, store_tokens, store_trader_counts,
+    store_unique_traders,
+};
 
-use abi::pool::events::{Burn, Mint, Swap, Sync};
+use abi::cl_pool::events::{Burn as ClBurn, Collect as ClCollect, Mint as ClMint};
+use abi::pool::events::{Burn, Fees, Mint, Swap, Sync};
+use abi::pool_factory::events::PoolCreated;
+use hex_literal::hex;
+use math::to_decimal;
 use pb::aerodrome::{
-    AerodromeLiquidity, AerodromeSwap, LiquidityEvents, SwapEvents, SyncEvent, SyncEvents,
+    AerodromeLiquidity, AerodromeSwap, Candles, ClLiquidityDepth, ClPosition, ClSwapEvents,
+    EmissionEvents, GaugeEvents, GovernanceEvents, LiquidityEvents, Pool, PoolFeeEvents,
+    PoolSnapshots, PoolTvlSnapshots, Pools, SwapEvents, SyncEvent, SyncEvents, Token, VeLock,
+    VeLockEvents,
+};
+use std::collections::HashMap;
+use substreams::pb::substreams::store_delta::Operation;
+use substreams::scalar::{BigDecimal, BigInt};
+use substreams::store::{
+    DeltaBigDecimal, DeltaBigInt, DeltaProto, Deltas, StoreGet, StoreGetBigDecimal,
+    StoreGetBigInt, StoreGetInt64, StoreGetProto,
 };
+use swaps::SwapDirection;
 use substreams::Hex;
 use substreams_database_change::pb::database::DatabaseChanges;
-use substreams_database_change::tables::Tables;
+use substreams_database_change::tables::{Tables, ToDatabaseValue};
 use substreams_ethereum::pb::eth::v2 as eth;
 use substreams_ethereum::Event;
 
-/// Extract Aerodrome swap events from blocks
+/// Aerodrome PoolFactory on Base
+const POOL_FACTORY: [u8; 20] = hex!("420dd381b31aef6683db6b902084cb0ffece40da");
+
+/// Extract pools created by the Aerodrome PoolFactory
 #[substreams::handlers::map]
-pub fn map_swaps(blk: eth::Block) -> Result<SwapEvents, substreams::errors::Error> {
+pub fn map_pools_created(blk: eth::Block) -> Result<Pools, substreams::errors::Error> {
     let timestamp = blk
         .header
         .as_ref()
@@ -31,7 +97,7 @@ pub fn map_swaps(blk: eth::Block) -> Result<SwapEvents, substreams::errors::Erro
         .map(|t| t.seconds)
         .unwrap_or(0);
 
-    let swaps: Vec<AerodromeSwap> = blk
+    let pools: Vec<Pool> = blk
         .receipts()
         .flat_map(|receipt| {
             let tx_hash = Hex(&receipt.transaction.hash).to_string();
@@ -39,43 +105,241 @@ pub fn map_swaps(blk: eth::Block) -> Result<SwapEvents, substreams::errors::Erro
                 .receipt
                 .logs
                 .iter()
+                .filter(|log| log.address == POOL_FACTORY)
                 .filter_map(move |log| {
-                    let swap = Swap::match_and_decode(log)?;
-                    Some(AerodromeSwap {
+                    let created = PoolCreated::match_and_decode(log)?;
+                    Some(Pool {
+                        address: Hex(&created.pool).to_string(),
+                        token0: Hex(&created.token0).to_string(),
+                        token1: Hex(&created.token1).to_string(),
+                        stable: created.stable,
                         block_number: blk.number,
                         transaction_hash: tx_hash.clone(),
-                        log_index: log.index as u64,
-                        pool_address: Hex(&log.address).to_string(),
-                        sender: Hex(&swap.sender).to_string(),
-                        recipient: Hex(&swap.to).to_string(),
-                        amount0_in: swap.amount0_in.to_string(),
-                        amount1_in: swap.amount1_in.to_string(),
-                        amount0_out: swap.amount0_out.to_string(),
-                        amount1_out: swap.amount1_out.to_string(),
                         timestamp: timestamp as u64,
+                        tick_spacing: 0,
+                        log_index: log.index as u64,
+                        ordinal: log.ordinal,
                     })
                 })
         })
         .collect();
 
+    Ok(Pools { pools })
+}
+
+/// Look up the pool emitting a log, `None` if the store's factory did not create it
+fn aerodrome_pool(pools: &StoreGetProto<Pool>, address: &[u8]) -> Option<Pool> {
+    pools.get_last(format!("pool:{}", Hex(address)))
+}
+
+/// Decimals of a pool token, zero (unscaled) when its metadata is unknown
+fn token_decimals(tokens: &StoreGetProto<Token>, address: &str) -> u64 {
+    tokens
+        .get_last(format!("token:{}", address))
+        .map(|token| token.decimals)
+        .unwrap_or(0)
+}
+
+/// Decimal-adjusted reserves of a pool as of `ordinal`
+fn pool_reserves(
+    reserves: &StoreGetBigInt,
+    ordinal: u64,
+    pool: &str,
+    decimals0: u64,
+    decimals1: u64,
+) -> Option<(BigDecimal, BigDecimal)> {
+    let reserve0 = reserves.get_at(ordinal, format!("pool:{}:reserve0", pool))?;
+    let reserve1 = reserves.get_at(ordinal, format!("pool:{}:reserve1", pool))?;
+    Some((
+        to_decimal(&reserve0.to_string(), decimals0),
+        to_decimal(&reserve1.to_string(), decimals1),
+    ))
+}
+
+/// Optional decimal as a proto string, empty when undefined
+fn decimal_string(value: Option<BigDecimal>) -> String {
+    value.map(|v| v.to_string()).unwrap_or_default()
+}
+
+/// Extract Aerodrome swap events from blocks
+///
+/// Pool.swap() emits Sync before Swap, so the pre-swap mid-price is read
+/// from the reserves just before the Sync paired with each Swap.
+///
+/// The LP fee taken on each input side is emitted as a `Fees` log ahead of
+/// the Sync, so fees are accumulated per pool until the Swap consumes them.
+/// The fee rate and token USD prices are the ones in effect at the Swap's
+/// ordinal.
+#[substreams::handlers::map]
+pub fn map_swaps(
+    blk: eth::Block,
+    pools: StoreGetProto<Pool>,
+    tokens: StoreGetProto<Token>,
+    reserves: StoreGetBigInt,
+    pool_fees: StoreGetInt64,
+    token_prices: StoreGetBigDecimal,
+) -> Result<SwapEvents, substreams::errors::Error> {
+    let timestamp = blk
+        .header
+        .as_ref()
+        .and_then(|h| h.timestamp.as_ref())
+        .map(|t| t.seconds)
+        .unwrap_or(0);
+
+    let mut swaps = Vec::new();
+
+    for receipt in blk.receipts() {
+        let tx_hash = Hex(&receipt.transaction.hash).to_string();
+        // Ordinal of the latest Sync seen for each pool in this transaction
+        let mut last_sync: HashMap<&[u8], u64> = HashMap::new();
+        // Fees logged by each pool since its last Swap in this transaction
+        let mut pending_fees: HashMap<&[u8], (BigInt, BigInt)> = HashMap::new();
+
+        for log in &receipt.receipt.logs {
+            if Sync::match_log(log) {
+                last_sync.insert(&log.address, log.ordinal);
+                continue;
+            }
+            if let Some(fees) = Fees::match_and_decode(log) {
+                let pending = pending_fees
+                    .entry(&log.address)
+                    .or_insert_with(|| (BigInt::zero(), BigInt::zero()));
+                pending.0 = pending.0.clone() + fees.amount0;
+                pending.1 = pending.1.clone() + fees.amount1;
+                continue;
+            }
+            let Some(swap) = Swap::match_and_decode(log) else {
+                continue;
+            };
+            let (fee0, fee1) = pending_fees
+                .remove(log.address.as_slice())
+                .unwrap_or_else(|| (BigInt::zero(), BigInt::zero()));
+            let Some(pool) = aerodrome_pool(&pools, &log.address) else {
+                continue;
+            };
+            let decimals0 = token_decimals(&tokens, &pool.token0);
+            let decimals1 = token_decimals(&tokens, &pool.token1);
+            let fee0 = fee0.to_string();
+            let fee1 = fee1.to_string();
+            let fee_rate = fees::fee_in_effect(&pool_fees, log.ordinal, &pool.address, pool.stable);
+
+            // Normalized trade view: what the trader sold and what they received
+            let direction = SwapDirection::new(
+                &swap.amount0_in,
+                &swap.amount1_in,
+                &swap.amount0_out,
+                &swap.amount1_out,
+            );
+            let (token_in, token_out, decimals_in, decimals_out) = if direction.zero_for_one {
+                (&pool.token0, &pool.token1, decimals0, decimals1)
+            } else {
+                (&pool.token1, &pool.token0, decimals1, decimals0)
+            };
+            let amount_in = direction.amount_in.to_string();
+            let amount_out = direction.amount_out.to_string();
+
+            let amount0_in = swap.amount0_in.to_string();
+            let amount1_in = swap.amount1_in.to_string();
+            let amount0_out = swap.amount0_out.to_string();
+            let amount1_out = swap.amount1_out.to_string();
+
+            // Execution prices from the decimal-adjusted trade amounts
+            let amount_in_decimal = to_decimal(&amount_in, decimals_in);
+            let amount_out_decimal = to_decimal(&amount_out, decimals_out);
+            let (amount0, amount1) = if direction.zero_for_one {
+                (&amount_in_decimal, &amount_out_decimal)
+            } else {
+                (&amount_out_decimal, &amount_in_decimal)
+            };
+            let price0 = prices::ratio(amount1, amount0);
+            let price1 = prices::ratio(amount0, amount1);
+            let amount_usd = pricing::swap_value_usd(
+                &amount_in_decimal,
+                pricing::usd_price(&token_prices, log.ordinal, token_in),
+                &amount_out_decimal,
+                pricing::usd_price(&token_prices, log.ordinal, token_out),
+            );
+
+            // Mid-price from the reserves before this swap's Sync
+            let mid_price0 = last_sync
+                .get(log.address.as_slice())
+                .and_then(|ordinal| {
+                    let before = ordinal.saturating_sub(1);
+                    pool_reserves(&reserves, before, &pool.address, decimals0, decimals1)
+                })
+                .and_then(|(reserve0, reserve1)| {
+                    prices::mid_price0(&reserve0, &reserve1, pool.stable)
+                });
+            let mid_price1 = mid_price0
+                .as_ref()
+                .and_then(|mid| prices::ratio(&BigDecimal::one(), mid));
+            let (execution, mid) = if direction.zero_for_one {
+                (&price0, &mid_price0)
+            } else {
+                (&price1, &mid_price1)
+            };
+            let price_impact = match (execution, mid) {
+                (Some(execution), Some(mid)) => prices::price_impact(execution, mid),
+                _ => None,
+            };
+
+            swaps.push(AerodromeSwap {
+                block_number: blk.number,
+                transaction_hash: tx_hash.clone(),
+                log_index: log.index as u64,
+                pool_address: pool.address.clone(),
+                sender: Hex(&swap.sender).to_string(),
+                recipient: Hex(&swap.to).to_string(),
+                amount0_in_decimal: to_decimal(&amount0_in, decimals0).to_string(),
+                amount1_in_decimal: to_decimal(&amount1_in, decimals1).to_string(),
+                amount0_out_decimal: to_decimal(&amount0_out, decimals0).to_string(),
+                amount1_out_decimal: to_decimal(&amount1_out, decimals1).to_string(),
+                amount0_in,
+                amount1_in,
+                amount0_out,
+                amount1_out,
+                timestamp: timestamp as u64,
+                token_in: token_in.clone(),
+                token_out: token_out.clone(),
+                amount_in_decimal: amount_in_decimal.to_string(),
+                amount_out_decimal: amount_out_decimal.to_string(),
+                amount_in,
+                amount_out,
+                two_sided: direction.two_sided,
+                price0: decimal_string(price0),
+                price1: decimal_string(price1),
+                mid_price0: decimal_string(mid_price0),
+                mid_price1: decimal_string(mid_price1),
+                price_impact: decimal_string(price_impact),
+                fee0_decimal: to_decimal(&fee0, decimals0).to_string(),
+                fee1_decimal: to_decimal(&fee1, decimals1).to_string(),
+                fee0,
+                fee1,
+                fee_rate,
+                amount_usd: decimal_string(amount_usd),
+                token0: pool.token0,
+                token1: pool.token1,
+                ordinal: log.ordinal,
+            });
+        }
+    }
+
     let swap_count = swaps.len() as u32;
-    let total_volume = swaps.iter().fold(0u64, |acc, s| {
-        acc.saturating_add(
-            s.amount0_in.parse::<u64>().unwrap_or(0)
-                + s.amount1_in.parse::<u64>().unwrap_or(0),
-        )
-    });
-
-    Ok(SwapEvents {
-        swaps,
-        swap_count,
-        total_volume,
-    })
+
+    Ok(SwapEvents { swaps, swap_count })
 }
 
 /// Extract liquidity events (Mint/Burn)
+///
+/// Covers V2-style pools and Slipstream pools; for the latter the pool-level
+/// Collect is reported too, along with the tick range and liquidity.
 #[substreams::handlers::map]
-pub fn map_liquidity(blk: eth::Block) -> Result<LiquidityEvents, substreams::errors::Error> {
+pub fn map_liquidity(
+    blk: eth::Block,
+    pools: StoreGetProto<Pool>,
+    cl_pools: StoreGetProto<Pool>,
+    tokens: StoreGetProto<Token>,
+) -> Result<LiquidityEvents, substreams::errors::Error> {
     let timestamp = blk
         .header
         .as_ref()
@@ -89,37 +353,61 @@ pub fn map_liquidity(blk: eth::Block) -> Result<LiquidityEvents, substreams::err
         let tx_hash = Hex(&receipt.transaction.hash).to_string();
 
         for log in &receipt.receipt.logs {
-            // Mint events (add liquidity)
-            if let Some(mint) = Mint::match_and_decode(log) {
-                events.push(AerodromeLiquidity {
-                    block_number: blk.number,
-                    transaction_hash: tx_hash.clone(),
-                    log_index: log.index as u64,
-                    pool_address: Hex(&log.address).to_string(),
-                    sender: Hex(&mint.sender).to_string(),
-                    recipient: Hex(&mint.to).to_string(),
-                    amount0: mint.amount0.to_string(),
-                    amount1: mint.amount1.to_string(),
-                    action: "mint".to_string(),
-                    timestamp: timestamp as u64,
-                });
-            }
+            // Mint (add liquidity) and Burn (remove liquidity) events
+            let (sender, recipient, amount0, amount1, action, range) =
+                if let Some(mint) = Mint::match_and_decode(log) {
+                    // Aerodrome's Mint does not carry the LP token recipient
+                    (mint.sender, Vec::new(), mint.amount0, mint.amount1, "mint", None)
+                } else if let Some(burn) = Burn::match_and_decode(log) {
+                    (burn.sender, burn.to, burn.amount0, burn.amount1, "burn", None)
+                } else if let Some(mint) = ClMint::match_and_decode(log) {
+                    let range = (mint.tick_lower, mint.tick_upper, mint.amount);
+                    (mint.sender, mint.owner, mint.amount0, mint.amount1, "mint", Some(range))
+                } else if let Some(burn) = ClBurn::match_and_decode(log) {
+                    let range = (burn.tick_lower, burn.tick_upper, burn.amount);
+                    (burn.owner, Vec::new(), burn.amount0, burn.amount1, "burn", Some(range))
+                } else if let Some(collect) = ClCollect::match_and_decode(log) {
+                    let range = (collect.tick_lower, collect.tick_upper, BigInt::zero());
+                    let (owner, recipient) = (collect.owner, collect.recipient);
+                    (owner, recipient, collect.amount0, collect.amount1, "collect", Some(range))
+                } else {
+                    continue;
+                };
 
-            // Burn events (remove liquidity)
-            if let Some(burn) = Burn::match_and_decode(log) {
-                events.push(AerodromeLiquidity {
-                    block_number: blk.number,
-                    transaction_hash: tx_hash.clone(),
-                    log_index: log.index as u64,
-                    pool_address: Hex(&log.address).to_string(),
-                    sender: Hex(&burn.sender).to_string(),
-                    recipient: Hex(&burn.to).to_string(),
-                    amount0: burn.amount0.to_string(),
-                    amount1: burn.amount1.to_string(),
-                    action: "burn".to_string(),
-                    timestamp: timestamp as u64,
-                });
+            let pool_store = if range.is_some() { &cl_pools } else { &pools };
+            let Some(pool) = aerodrome_pool(pool_store, &log.address) else {
+                continue;
+            };
+            let amount0 = amount0.to_string();
+            let amount1 = amount1.to_string();
+
+            let mut event = AerodromeLiquidity {
+                block_number: blk.number,
+                transaction_hash: tx_hash.clone(),
+                log_index: log.index as u64,
+                pool_address: pool.address,
+                sender: Hex(&sender).to_string(),
+                recipient: Hex(&recipient).to_string(),
+                amount0_decimal: to_decimal(&amount0, token_decimals(&tokens, &pool.token0))
+                    .to_string(),
+                amount1_decimal: to_decimal(&amount1, token_decimals(&tokens, &pool.token1))
+                    .to_string(),
+                amount0,
+                amount1,
+                action: action.to_string(),
+                timestamp: timestamp as u64,
+                token0: pool.token0,
+                token1: pool.token1,
+                ..Default::default()
+            };
+            if let Some((tick_lower, tick_upper, liquidity)) = range {
+                event.concentrated = true;
+                event.ordinal = log.ordinal;
+                event.tick_lower = tick_lower.to_i32();
+                event.tick_upper = tick_upper.to_i32();
+                event.liquidity = liquidity.to_string();
             }
+            events.push(event);
         }
     }
 
@@ -132,7 +420,10 @@ pub fn map_liquidity(blk: eth::Block) -> Result<LiquidityEvents, substreams::err
 
 /// Extract Sync events (reserve updates)
 #[substreams::handlers::map]
-pub fn map_syncs(blk: eth::Block) -> Result<SyncEvents, substreams::errors::Error> {
+pub fn map_syncs(
+    blk: eth::Block,
+    pools: StoreGetProto<Pool>,
+) -> Result<SyncEvents, substreams::errors::Error> {
     let timestamp = blk
         .header
         .as_ref()
@@ -140,19 +431,29 @@ pub fn map_syncs(blk: eth::Block) -> Result<SyncEvents, substreams::errors::Erro
         .map(|t| t.seconds)
         .unwrap_or(0);
 
+    let pools = &pools;
     let syncs: Vec<SyncEvent> = blk
         .receipts()
         .flat_map(|receipt| {
-            receipt.receipt.logs.iter().filter_map(move |log| {
-                let sync = Sync::match_and_decode(log)?;
-                Some(SyncEvent {
-                    block_number: blk.number,
-                    pool_address: Hex(&log.address).to_string(),
-                    reserve0: sync.reserve0.to_string(),
-                    reserve1: sync.reserve1.to_string(),
-                    timestamp: timestamp as u64,
+            let tx_hash = Hex(&receipt.transaction.hash).to_string();
+            receipt
+                .receipt
+                .logs
+                .iter()
+                .filter_map(move |log| {
+                    let sync = Sync::match_and_decode(log)?;
+                    aerodrome_pool(pools, &log.address)?;
+                    Some(SyncEvent {
+                        block_number: blk.number,
+                        pool_address: Hex(&log.address).to_string(),
+                        reserve0: sync.reserve0.to_string(),
+                        reserve1: sync.reserve1.to_string(),
+                        timestamp: timestamp as u64,
+                        transaction_hash: tx_hash.clone(),
+                        log_index: log.index as u64,
+                        ordinal: log.ordinal,
+                    })
                 })
-            })
         })
         .collect();
 
@@ -163,37 +464,78 @@ pub fn map_syncs(blk: eth::Block) -> Result<SyncEvents, substreams::errors::Erro
     })
 }
 
-/// Database sink output for swap events
+/// Database sink output
 ///
 /// Produces CDC records for:
-/// - Individual swaps (create_row)
-/// - Swap prices for candle building via SQL materialized views
+/// - Individual swaps, V2-style and Slipstream (create_row)
+/// - Liquidity events (Mint/Burn) and pool reserve updates (create_row)
+/// - Slipstream positions (upsert_row)
+/// - Slipstream tick liquidity and pool state (upsert_row)
+/// - veAERO lock events (create_row) and current locks (upsert_row)
+/// - Votes (create_row), pool vote weights per epoch (upsert_row)
+/// - Gauges (create_row, update_row when killed or revived) and whitelisted tokens (upsert_row)
+/// - Gauge events (create_row), staked supply and emissions (update_row, upsert_row per epoch)
+/// - Fees and bribes per pool, reward token and epoch (upsert_row)
+/// - Emissions, tail emission rate and rebases per epoch (upsert_row)
+/// - Pool fee rates set at creation and by the PoolFactory (create_row)
+/// - Pool and protocol TVL snapshots on every reserve change (create_row)
+/// - Token metadata of newly seen pool tokens (create_row) and USD prices (update_row)
+/// - OHLCV candles from `map_candles` (upsert_row)
+/// - Pool, trader, daily, hourly and protocol stats, in raw units and USD (upsert_row)
+/// - Per-pool volume and LP fees per day and epoch (upsert_row)
 ///
-/// Note: Aggregations (candles, stats) are computed via SQL materialized views
-/// in schema.sql and schema.clickhouse.sql
+/// Note: Aggregations are computed by the stores (set_if_not_exists, max,
+/// min, add); the sink upserts their latest values, so no SQL materialized
+/// views are needed to maintain candles and stats
 #[substreams::handlers::map]
-pub fn db_out(swaps: SwapEvents) -> Result<DatabaseChanges, substreams::errors::Error> {
+pub fn db_out(
+    swaps: SwapEvents,
+    cl_swaps: ClSwapEvents,
+    liquidity: LiquidityEvents,
+    syncs: SyncEvents,
+    tokens: Deltas<DeltaProto<Token>>,
+    candles: Candles,
+    volumes: Deltas<DeltaBigInt>,
+    positions: Deltas<DeltaProto<ClPosition>>,
+    depth: ClLiquidityDepth,
+    ve_events: VeLockEvents,
+    ve_locks: Deltas<DeltaProto<VeLock>>,
+    governance: GovernanceEvents,
+    pool_votes: Deltas<DeltaBigInt>,
+    gauge_events: GaugeEvents,
+    gauge_stakes: Deltas<DeltaBigInt>,
+    gauge_emissions: Deltas<DeltaBigInt>,
+    voting_rewards: Deltas<DeltaBigInt>,
+    emissions: EmissionEvents,
+    rebases: Deltas<DeltaBigInt>,
+    pool_fee_events: PoolFeeEvents,
+    pool_fees: StoreGetInt64,
+    volumes_usd: Deltas<DeltaBigDecimal>,
+    token_prices: Deltas<DeltaBigDecimal>,
+    tvl: PoolTvlSnapshots,
+    snapshots: PoolSnapshots,
+    trader_counts: Deltas<DeltaBigInt>,
+) -> Result<DatabaseChanges, substreams::errors::Error> {
     let mut tables = Tables::new();
 
+    for delta in tokens.deltas.iter() {
+        if delta.operation != Operation::Create {
+            continue;
+        }
+        let token = &delta.new_value;
+        tables
+            .create_row("tokens", &token.address)
+            .set("name", &token.name)
+            .set("symbol", &token.symbol)
+            .set("decimals", token.decimals);
+    }
+
     for swap in &swaps.swaps {
         // Create unique swap ID
         let swap_id = format!("{}:{}", swap.transaction_hash, swap.log_index);
 
-        // Calculate total amounts for price computation
-        let amount_in: u64 = swap.amount0_in.parse().unwrap_or(0)
-            + swap.amount1_in.parse().unwrap_or(0);
-        let amount_out: u64 = swap.amount0_out.parse().unwrap_or(0)
-            + swap.amount1_out.parse().unwrap_or(0);
-
-        // Calculate price ratio (scaled by 1M for precision)
-        let price_ratio = if amount_in > 0 {
-            ((amount_out as f64 / amount_in as f64) * 1_000_000.0) as i64
-        } else {
-            0
-        };
-
         // Insert individual swap with computed fields
-        tables
+        let row = tables
             .create_row("aerodrome_swaps", &swap_id)
             .set("tx_hash", &swap.transaction_hash)
             .set("log_index", swap.log_index)
@@ -206,10 +548,521 @@ pub fn db_out(swaps: SwapEvents) -> Result<DatabaseChanges, substreams::errors::
             .set("amount1_in", &swap.amount1_in)
             .set("amount0_out", &swap.amount0_out)
             .set("amount1_out", &swap.amount1_out)
-            .set("amount_in_total", amount_in)
-            .set("amount_out_total", amount_out)
-            .set("price_ratio", price_ratio);
+            .set("token0", &swap.token0)
+            .set("token1", &swap.token1)
+            .set("amount0_in_decimal", &swap.amount0_in_decimal)
+            .set("amount1_in_decimal", &swap.amount1_in_decimal)
+            .set("amount0_out_decimal", &swap.amount0_out_decimal)
+            .set("amount1_out_decimal", &swap.amount1_out_decimal)
+            .set("token_in", &swap.token_in)
+            .set("token_out", &swap.token_out)
+            .set("amount_in", &swap.amount_in)
+            .set("amount_out", &swap.amount_out)
+            .set("amount_in_decimal", &swap.amount_in_decimal)
+            .set("amount_out_decimal", &swap.amount_out_decimal)
+            .set("two_sided", swap.two_sided)
+            .set("fee0", &swap.fee0)
+            .set("fee1", &swap.fee1)
+            .set("fee0_decimal", &swap.fee0_decimal)
+            .set("fee1_decimal", &swap.fee1_decimal)
+            .set("fee_rate", swap.fee_rate);
+
+        // Prices and USD value are left NULL when undefined (e.g. no prior reserves)
+        for (column, price) in [
+            ("price0", &swap.price0),
+            ("price1", &swap.price1),
+            ("mid_price0", &swap.mid_price0),
+            ("mid_price1", &swap.mid_price1),
+            ("price_impact", &swap.price_impact),
+            ("amount_usd", &swap.amount_usd),
+        ] {
+            if !price.is_empty() {
+                row.set(column, price);
+            }
+        }
+
+        // Latest activity; swaps are in log order so the last one wins
+        tables
+            .upsert_row("pool_stats", &swap.pool_address)
+            .set("last_swap_block", swap.block_number)
+            .set("last_swap_time", swap.timestamp as i64);
+        if !swap.sender.is_empty() {
+            tables
+                .upsert_row("trader_stats", &swap.sender)
+                .set("last_swap_time", swap.timestamp as i64);
+        }
+    }
+
+    for swap in &cl_swaps.swaps {
+        let swap_id = format!("{}:{}", swap.transaction_hash, swap.log_index);
+
+        let row = tables
+            .create_row("aerodrome_cl_swaps", &swap_id)
+            .set("tx_hash", &swap.transaction_hash)
+            .set("log_index", swap.log_index)
+            .set("block_number", swap.block_number)
+            .set("timestamp", swap.timestamp as i64)
+            .set("pool_address", &swap.pool_address)
+            .set("tick_spacing", swap.tick_spacing)
+            .set("sender", &swap.sender)
+            .set("recipient", &swap.recipient)
+            .set("token0", &swap.token0)
+            .set("token1", &swap.token1)
+            .set("amount0", &swap.amount0)
+            .set("amount1", &swap.amount1)
+            .set("amount0_decimal", &swap.amount0_decimal)
+            .set("amount1_decimal", &swap.amount1_decimal)
+            .set("token_in", &swap.token_in)
+            .set("token_out", &swap.token_out)
+            .set("amount_in", &swap.amount_in)
+            .set("amount_out", &swap.amount_out)
+            .set("amount_in_decimal", &swap.amount_in_decimal)
+            .set("amount_out_decimal", &swap.amount_out_decimal)
+            .set("sqrt_price_x96", &swap.sqrt_price_x96)
+            .set("liquidity", &swap.liquidity)
+            .set("tick", swap.tick);
+
+        for (column, price) in [
+            ("price0", &swap.price0),
+            ("price1", &swap.price1),
+            ("pool_price0", &swap.pool_price0),
+        ] {
+            if !price.is_empty() {
+                row.set(column, price);
+            }
+        }
+    }
+
+    for event in &liquidity.events {
+        let event_id = format!("{}:{}", event.transaction_hash, event.log_index);
+
+        let row = tables
+            .create_row("aerodrome_liquidity_events", &event_id)
+            .set("tx_hash", &event.transaction_hash)
+            .set("log_index", event.log_index)
+            .set("block_number", event.block_number)
+            .set("timestamp", event.timestamp as i64)
+            .set("pool_address", &event.pool_address)
+            .set("action", &event.action)
+            .set("sender", &event.sender)
+            .set("recipient", &event.recipient)
+            .set("token0", &event.token0)
+            .set("token1", &event.token1)
+            .set("amount0", &event.amount0)
+            .set("amount1", &event.amount1)
+            .set("amount0_decimal", &event.amount0_decimal)
+            .set("amount1_decimal", &event.amount1_decimal);
+
+        if event.concentrated {
+            row.set("tick_lower", event.tick_lower)
+                .set("tick_upper", event.tick_upper)
+                .set("liquidity", &event.liquidity);
+        }
+    }
+
+    for delta in positions.deltas.iter() {
+        let position = &delta.new_value;
+        tables
+            .upsert_row("cl_positions", &position.token_id)
+            .set("pool_address", &position.pool_address)
+            .set("token0", &position.token0)
+            .set("token1", &position.token1)
+            .set("tick_lower", position.tick_lower)
+            .set("tick_upper", position.tick_upper)
+            .set("owner", &position.owner)
+            .set("liquidity", &position.liquidity)
+            .set("collected0", &position.collected0)
+            .set("collected1", &position.collected1)
+            .set("withdrawn0", &position.withdrawn0)
+            .set("withdrawn1", &position.withdrawn1);
+    }
+
+    for pool in &depth.pools {
+        tables
+            .upsert_row("cl_pool_state", &pool.pool_address)
+            .set("liquidity", &pool.liquidity)
+            .set("sqrt_price_x96", &pool.sqrt_price_x96)
+            .set("tick", pool.tick)
+            .set("block_number", depth.block_number)
+            .set("timestamp", depth.timestamp as i64);
+
+        for tick in &pool.ticks {
+            tables
+                .upsert_row(
+                    "cl_ticks",
+                    [
+                        ("pool_address", pool.pool_address.clone()),
+                        ("tick", tick.tick.to_string()),
+                    ],
+                )
+                .set("liquidity_net", &tick.liquidity_net)
+                .set("block_number", depth.block_number);
+        }
+    }
+
+    for event in &ve_events.events {
+        let event_id = format!(
+            "{}:{}:{}",
+            event.transaction_hash, event.log_index, event.token_id
+        );
+
+        tables
+            .create_row("ve_lock_events", &event_id)
+            .set("tx_hash", &event.transaction_hash)
+            .set("log_index", event.log_index)
+            .set("block_number", event.block_number)
+            .set("timestamp", event.timestamp as i64)
+            .set("token_id", &event.token_id)
+            .set("action", &event.action)
+            .set("owner", &event.owner)
+            .set("amount", &event.amount)
+            .set("unlock_time", event.unlock_time)
+            .set("deposit_type", &event.deposit_type)
+            .set("related_token_id", &event.related_token_id);
+    }
+
+    for delta in ve_locks.deltas.iter() {
+        let lock = &delta.new_value;
+        tables
+            .upsert_row("ve_locks", &lock.token_id)
+            .set("owner", &lock.owner)
+            .set("amount", &lock.amount)
+            .set("unlock_time", lock.unlock_time)
+            .set("permanent", lock.permanent);
+    }
+
+    for event in &governance.events {
+        match event.action.as_str() {
+            "vote" | "abstain" => {
+                let vote_id = format!("{}:{}", event.transaction_hash, event.log_index);
+                tables
+                    .create_row("votes", &vote_id)
+                    .set("tx_hash", &event.transaction_hash)
+                    .set("log_index", event.log_index)
+                    .set("block_number", event.block_number)
+                    .set("timestamp", event.timestamp as i64)
+                    .set("epoch", event.epoch)
+                    .set("action", &event.action)
+                    .set("voter", &event.voter)
+                    .set("token_id", &event.token_id)
+                    .set("pool_address", &event.pool_address)
+                    .set("weight", &event.amount)
+                    .set("pool_weight", &event.pool_weight);
+            }
+            "gauge_created" => {
+                tables
+                    .create_row("gauges", &event.gauge)
+                    .set("pool_address", &event.pool_address)
+                    .set("bribe_voting_reward", &event.bribe_voting_reward)
+                    .set("fees_voting_reward", &event.fees_voting_reward)
+                    .set("creator", &event.voter)
+                    .set("alive", true)
+                    .set("created_block", event.block_number)
+                    .set("created_timestamp", event.timestamp as i64);
+            }
+            "gauge_killed" | "gauge_revived" => {
+                tables
+                    .update_row("gauges", &event.gauge)
+                    .set("alive", event.action == "gauge_revived");
+            }
+            "whitelist_token" => {
+                tables
+                    .upsert_row("whitelisted_tokens", &event.token)
+                    .set("whitelisted", event.whitelisted)
+                    .set("block_number", event.block_number);
+            }
+            // Each distribution also emits the gauge's NotifyReward, which is
+            // sunk to gauge_events and gauge_emissions below
+            "distribute_reward" => {}
+            _ => {}
+        }
+    }
+
+    for delta in pool_votes.deltas.iter() {
+        let Some((epoch, pool)) = votes::parse_epoch_pool_key(&delta.key) else {
+            continue;
+        };
+        tables
+            .upsert_row(
+                "pool_votes",
+                [
+                    ("epoch", epoch.to_string()),
+                    ("pool_address", pool.to_string()),
+                ],
+            )
+            .set("weight", &delta.new_value);
+    }
+
+    for event in &gauge_events.events {
+        let event_id = format!("{}:{}", event.transaction_hash, event.log_index);
+        tables
+            .create_row("gauge_events", &event_id)
+            .set("tx_hash", &event.transaction_hash)
+            .set("log_index", event.log_index)
+            .set("block_number", event.block_number)
+            .set("timestamp", event.timestamp as i64)
+            .set("epoch", event.epoch)
+            .set("gauge", &event.gauge)
+            .set("pool_address", &event.pool_address)
+            .set("action", &event.action)
+            .set("account", &event.account)
+            .set("amount", &event.amount);
+    }
+
+    // Gauge rows are created from GaugeCreated above, so totals are updates
+    for delta in gauge_stakes.deltas.iter().chain(gauge_emissions.deltas.iter()) {
+        match gauges::gauge_column(&delta.key) {
+            Some((gauge, None, column)) => {
+                tables
+                    .update_row("gauges", gauge)
+                    .set(column, &delta.new_value);
+            }
+            Some((gauge, Some(epoch), column)) => {
+                tables
+                    .upsert_row(
+                        "gauge_emissions",
+                        [
+                            ("epoch", epoch.to_string()),
+                            ("gauge", gauge.to_string()),
+                        ],
+                    )
+                    .set(column, &delta.new_value);
+            }
+            None => {}
+        }
+    }
+
+    for delta in voting_rewards.deltas.iter() {
+        let Some(key) = rewards::parse_reward_key(&delta.key) else {
+            continue;
+        };
+        tables
+            .upsert_row(
+                "voting_rewards",
+                [
+                    ("epoch", key.epoch.to_string()),
+                    ("pool_address", key.pool.to_string()),
+                    ("reward_type", key.reward_type.to_string()),
+                    ("token", key.token.to_string()),
+                ],
+            )
+            .set(key.column, &delta.new_value);
+    }
+
+    for event in &emissions.events {
+        match event.action.as_str() {
+            "mint" => {
+                tables
+                    .upsert_row("epoch_emissions", event.epoch.to_string())
+                    .set("epoch_start", calendar::epoch_start(event.epoch))
+                    .set("emissions", &event.amount)
+                    .set("circulating_supply", &event.circulating_supply)
+                    .set("tail", event.tail)
+                    .set("block_number", event.block_number);
+            }
+            "nudge" => {
+                tables
+                    .upsert_row("epoch_emissions", event.epoch.to_string())
+                    .set("epoch_start", calendar::epoch_start(event.epoch))
+                    .set("tail_emission_rate", &event.new_rate);
+            }
+            _ => {}
+        }
+    }
+
+    for delta in rebases.deltas.iter() {
+        let Some((epoch, column)) = emissions::parse_rebase_key(&delta.key) else {
+            continue;
+        };
+        tables
+            .upsert_row("epoch_emissions", epoch.to_string())
+            .set("epoch_start", calendar::epoch_start(epoch))
+            .set(column, &delta.new_value);
+    }
+
+    for event in &pool_fee_events.events {
+        let (id, fee_rate) = if event.action == "default_fee" {
+            let id = format!("{}:call:{}", event.transaction_hash, event.log_index);
+            (id, event.fee)
+        } else {
+            let id = format!("{}:{}", event.transaction_hash, event.log_index);
+            let rate =
+                fees::fee_in_effect(&pool_fees, event.ordinal, &event.pool_address, event.stable);
+            (id, rate)
+        };
+        let row = tables
+            .create_row("pool_fee_history", id)
+            .set("block_number", event.block_number)
+            .set("timestamp", event.timestamp as i64)
+            .set("tx_hash", &event.transaction_hash)
+            .set("action", &event.action)
+            .set("stable", event.stable)
+            .set("fee", event.fee)
+            .set("fee_rate", fee_rate);
+        // Default fee changes apply to every pool of the tier
+        if !event.pool_address.is_empty() {
+            row.set("pool_address", &event.pool_address);
+        }
+    }
+
+    for pool in &tvl.pools {
+        let row = tables
+            .create_row("pool_tvl", format!("{}:{}", pool.pool_address, tvl.block_number))
+            .set("pool_address", &pool.pool_address)
+            .set("block_number", tvl.block_number)
+            .set("timestamp", tvl.timestamp as i64)
+            .set("token0", &pool.token0)
+            .set("token1", &pool.token1)
+            .set("reserve0", &pool.reserve0)
+            .set("reserve1", &pool.reserve1)
+            .set("reserve0_decimal", &pool.reserve0_decimal)
+            .set("reserve1_decimal", &pool.reserve1_decimal);
+        // Left NULL until one of the pool's tokens is priced
+        if !pool.tvl_usd.is_empty() {
+            row.set("tvl_usd", &pool.tvl_usd);
+        }
+    }
+    if !tvl.pools.is_empty() {
+        tables
+            .create_row("protocol_tvl", tvl.block_number.to_string())
+            .set("timestamp", tvl.timestamp as i64)
+            .set("tvl_usd", &tvl.protocol_tvl_usd)
+            .set("pools_changed", tvl.pools.len() as u64);
+    }
+
+    for snapshot in &snapshots.snapshots {
+        let (table, bucket_column) = if snapshot.interval_seconds == 3600 {
+            ("pool_hour_data", "hour")
+        } else {
+            ("pool_day_data", "date")
+        };
+        let row = tables
+            .upsert_row(
+                table,
+                [
+                    (bucket_column, snapshot.bucket.clone()),
+                    ("pool_address", snapshot.pool_address.clone()),
+                ],
+            )
+            .set("period_start", snapshot.period_start)
+            .set("open_reserve0", &snapshot.open_reserve0)
+            .set("open_reserve1", &snapshot.open_reserve1)
+            .set("close_reserve0", &snapshot.close_reserve0)
+            .set("close_reserve1", &snapshot.close_reserve1)
+            .set("volume0", &snapshot.volume0)
+            .set("volume1", &snapshot.volume1)
+            .set("volume_usd", &snapshot.volume_usd)
+            .set("fees0", &snapshot.fees0)
+            .set("fees1", &snapshot.fees1)
+            .set("swap_count", snapshot.swap_count)
+            .set("unique_traders", snapshot.unique_traders)
+            .set("closed", snapshot.closed);
+        // Left NULL until one of the pool's tokens is priced
+        if !snapshot.tvl_usd.is_empty() {
+            row.set("tvl_usd", &snapshot.tvl_usd);
+        }
+    }
+
+    for sync in &syncs.events {
+        let sync_id = format!("{}:{}", sync.transaction_hash, sync.log_index);
+
+        tables
+            .create_row("aerodrome_pool_reserves", &sync_id)
+            .set("tx_hash", &sync.transaction_hash)
+            .set("log_index", sync.log_index)
+            .set("block_number", sync.block_number)
+            .set("timestamp", sync.timestamp as i64)
+            .set("pool_address", &sync.pool_address)
+            .set("reserve0", &sync.reserve0)
+            .set("reserve1", &sync.reserve1);
+    }
+
+    for candle in &candles.candles {
+        let row = tables
+            .upsert_row(
+                "candles",
+                [
+                    ("pool_address", candle.pool_address.clone()),
+                    ("interval_seconds", candle.interval_seconds.to_string()),
+                    ("timestamp", candle.timestamp.to_string()),
+                ],
+            )
+            .set("volume0", &candle.volume0)
+            .set("volume1", &candle.volume1)
+            .set("trade_count", candle.trade_count)
+            .set("closed", candle.closed);
+
+        // A candle without a priced swap keeps NULL prices
+        for (column, price) in [
+            ("open", &candle.open),
+            ("high", &candle.high),
+            ("low", &candle.low),
+            ("close", &candle.close),
+        ] {
+            if !price.is_empty() {
+                row.set(column, price);
+            }
+        }
+    }
+
+    // Store totals after this block's additions
+    for delta in volumes.deltas.iter() {
+        upsert_stats(&mut tables, &delta.key, &delta.new_value);
+    }
+    for delta in volumes_usd.deltas.iter() {
+        upsert_stats(&mut tables, &delta.key, &delta.new_value);
+    }
+    for delta in trader_counts.deltas.iter() {
+        if let Some((table, id, column)) = sink::trader_count_column(&delta.key) {
+            upsert_stats_row(&mut tables, table, id, column, &delta.new_value);
+        }
+    }
+
+    for delta in token_prices.deltas.iter() {
+        let Some(token) = delta.key.strip_prefix("token:") else {
+            continue;
+        };
+        tables
+            .update_row("tokens", token)
+            .set("price_usd", &delta.new_value);
     }
 
     Ok(tables.to_database_changes())
 }
+
+/// Upsert the aggregate row fed by a `store_swap_volumes` or
+/// `store_swap_volumes_usd` key
+fn upsert_stats<T: ToDatabaseValue>(tables: &mut Tables, key: &str, value: T) {
+    if let Some((table, bucket, pool, column)) = sink::pool_bucket_column(key) {
+        let bucket_column = if table == "pool_epoch_stats" { "epoch" } else { "date" };
+        tables
+            .upsert_row(
+                table,
+                [
+                    (bucket_column, bucket.to_string()),
+                    ("pool_address", pool.to_string()),
+                ],
+            )
+            .set(column, value);
+        return;
+    }
+    if let Some((table, id, column)) = sink::stats_column(key) {
+        upsert_stats_row(tables, table, id, column, value);
+    }
+}
+
+/// Upsert one column of an aggregate row keyed by a single id
+fn upsert_stats_row<T: ToDatabaseValue>(
+    tables: &mut Tables,
+    table: &str,
+    id: &str,
+    column: &str,
+    value: T,
+) {
+    let row = tables.upsert_row(table, id).set(column, value);
+    if table == "epoch_stats" {
+        if let Ok(epoch) = id.parse() {
+            row.set("epoch_start", calendar::epoch_start(epoch));
+        }
+    }
+}
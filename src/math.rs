@@ -0,0 +1,43 @@
+//! Exact arithmetic helpers for on-chain amounts
+//!
+//! Amounts are carried as decimal strings in the protobuf messages and are
+//! always parsed back into `BigInt`/`BigDecimal`, never into fixed-width
+//! integers, so uint256 values cannot overflow.
+
+use substreams::scalar::{BigDecimal, BigInt};
+
+/// Parse a decimal amount string into a `BigInt`, defaulting to zero
+pub fn parse_bigint(value: &str) -> BigInt {
+    value.parse::<BigInt>().unwrap_or_else(|_| BigInt::zero())
+}
+
+/// Scale a raw token amount string down by the token's decimals
+///
+/// The result keeps exactly `decimals` fractional digits.
+pub fn to_decimal(value: &str, decimals: u64) -> BigDecimal {
+    BigDecimal::new(parse_bigint(value), -(decimals as i64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_bigint_above_u64() {
+        // 1,000,000 WETH in wei does not fit into a u64
+        let amount = parse_bigint("1000000000000000000000000");
+        assert_eq!(amount.to_string(), "1000000000000000000000000");
+        assert_eq!(parse_bigint("not a number"), BigInt::zero());
+    }
+
+    #[test]
+    fn test_to_decimal() {
+        assert_eq!(to_decimal("1500000", 6).to_string(), "1.500000");
+        assert_eq!(
+            to_decimal("2500000000000000000", 18).to_string(),
+            "2.500000000000000000"
+        );
+        // Unknown decimals leave the amount unscaled
+        assert_eq!(to_decimal("42", 0).to_string(), "42");
+    }
+}
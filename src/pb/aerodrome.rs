@@ -8,8 +8,6 @@ pub struct SwapEvents {
     pub swaps: ::prost::alloc::vec::Vec<AerodromeSwap>,
     #[prost(uint32, tag="2")]
     pub swap_count: u32,
-    #[prost(uint64, tag="3")]
-    pub total_volume: u64,
 }
 /// Individual Aerodrome Swap event
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -37,6 +35,69 @@ pub struct AerodromeSwap {
     pub amount1_out: ::prost::alloc::string::String,
     #[prost(uint64, tag="11")]
     pub timestamp: u64,
+    #[prost(string, tag="12")]
+    pub token0: ::prost::alloc::string::String,
+    #[prost(string, tag="13")]
+    pub token1: ::prost::alloc::string::String,
+    /// Amounts normalized by token decimals
+    #[prost(string, tag="14")]
+    pub amount0_in_decimal: ::prost::alloc::string::String,
+    #[prost(string, tag="15")]
+    pub amount1_in_decimal: ::prost::alloc::string::String,
+    #[prost(string, tag="16")]
+    pub amount0_out_decimal: ::prost::alloc::string::String,
+    #[prost(string, tag="17")]
+    pub amount1_out_decimal: ::prost::alloc::string::String,
+    /// Directional trade derived from the net flow of each reserve side
+    #[prost(string, tag="18")]
+    pub token_in: ::prost::alloc::string::String,
+    #[prost(string, tag="19")]
+    pub token_out: ::prost::alloc::string::String,
+    #[prost(string, tag="20")]
+    pub amount_in: ::prost::alloc::string::String,
+    #[prost(string, tag="21")]
+    pub amount_out: ::prost::alloc::string::String,
+    #[prost(string, tag="22")]
+    pub amount_in_decimal: ::prost::alloc::string::String,
+    #[prost(string, tag="23")]
+    pub amount_out_decimal: ::prost::alloc::string::String,
+    /// both tokens went in (or out), e.g. flash swaps
+    #[prost(bool, tag="24")]
+    pub two_sided: bool,
+    /// Decimal-adjusted prices
+    ///
+    /// execution price of token0 in token1
+    #[prost(string, tag="25")]
+    pub price0: ::prost::alloc::string::String,
+    /// execution price of token1 in token0
+    #[prost(string, tag="26")]
+    pub price1: ::prost::alloc::string::String,
+    /// pre-swap mid-price of token0 in token1
+    #[prost(string, tag="27")]
+    pub mid_price0: ::prost::alloc::string::String,
+    /// pre-swap mid-price of token1 in token0
+    #[prost(string, tag="28")]
+    pub mid_price1: ::prost::alloc::string::String,
+    /// 1 - execution / mid-price in trade direction
+    #[prost(string, tag="29")]
+    pub price_impact: ::prost::alloc::string::String,
+    /// LP fees charged on the input side(s), from the pool's Fees log
+    #[prost(string, tag="30")]
+    pub fee0: ::prost::alloc::string::String,
+    #[prost(string, tag="31")]
+    pub fee1: ::prost::alloc::string::String,
+    #[prost(string, tag="32")]
+    pub fee0_decimal: ::prost::alloc::string::String,
+    #[prost(string, tag="33")]
+    pub fee1_decimal: ::prost::alloc::string::String,
+    /// LP fee in effect, in basis points
+    #[prost(uint64, tag="34")]
+    pub fee_rate: u64,
+    /// USD value, empty when neither token is priced
+    #[prost(string, tag="35")]
+    pub amount_usd: ::prost::alloc::string::String,
+    #[prost(uint64, tag="36")]
+    pub ordinal: u64,
 }
 /// Liquidity event collection (Mint/Burn)
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -67,11 +128,289 @@ pub struct AerodromeLiquidity {
     pub amount0: ::prost::alloc::string::String,
     #[prost(string, tag="8")]
     pub amount1: ::prost::alloc::string::String,
-    /// "mint" or "burn"
+    /// "mint", "burn" or "collect" (Slipstream only)
     #[prost(string, tag="9")]
     pub action: ::prost::alloc::string::String,
     #[prost(uint64, tag="10")]
     pub timestamp: u64,
+    #[prost(string, tag="11")]
+    pub token0: ::prost::alloc::string::String,
+    #[prost(string, tag="12")]
+    pub token1: ::prost::alloc::string::String,
+    /// Amounts normalized by token decimals
+    #[prost(string, tag="13")]
+    pub amount0_decimal: ::prost::alloc::string::String,
+    #[prost(string, tag="14")]
+    pub amount1_decimal: ::prost::alloc::string::String,
+    /// Slipstream pools only
+    #[prost(bool, tag="15")]
+    pub concentrated: bool,
+    #[prost(int32, tag="16")]
+    pub tick_lower: i32,
+    #[prost(int32, tag="17")]
+    pub tick_upper: i32,
+    /// liquidity minted or burned
+    #[prost(string, tag="18")]
+    pub liquidity: ::prost::alloc::string::String,
+    #[prost(uint64, tag="19")]
+    pub ordinal: u64,
+}
+/// Slipstream concentrated-liquidity swap collection
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ClSwapEvents {
+    #[prost(message, repeated, tag="1")]
+    pub swaps: ::prost::alloc::vec::Vec<AerodromeClSwap>,
+    #[prost(uint32, tag="2")]
+    pub swap_count: u32,
+}
+/// Individual Slipstream CLPool Swap event
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AerodromeClSwap {
+    #[prost(uint64, tag="1")]
+    pub block_number: u64,
+    #[prost(string, tag="2")]
+    pub transaction_hash: ::prost::alloc::string::String,
+    #[prost(uint64, tag="3")]
+    pub log_index: u64,
+    #[prost(string, tag="4")]
+    pub pool_address: ::prost::alloc::string::String,
+    #[prost(string, tag="5")]
+    pub sender: ::prost::alloc::string::String,
+    #[prost(string, tag="6")]
+    pub recipient: ::prost::alloc::string::String,
+    /// signed, positive when paid into the pool
+    #[prost(string, tag="7")]
+    pub amount0: ::prost::alloc::string::String,
+    /// signed, positive when paid into the pool
+    #[prost(string, tag="8")]
+    pub amount1: ::prost::alloc::string::String,
+    #[prost(string, tag="9")]
+    pub sqrt_price_x96: ::prost::alloc::string::String,
+    #[prost(string, tag="10")]
+    pub liquidity: ::prost::alloc::string::String,
+    #[prost(int32, tag="11")]
+    pub tick: i32,
+    #[prost(uint64, tag="12")]
+    pub timestamp: u64,
+    #[prost(string, tag="13")]
+    pub token0: ::prost::alloc::string::String,
+    #[prost(string, tag="14")]
+    pub token1: ::prost::alloc::string::String,
+    #[prost(int32, tag="15")]
+    pub tick_spacing: i32,
+    /// Amounts normalized by token decimals
+    #[prost(string, tag="16")]
+    pub amount0_decimal: ::prost::alloc::string::String,
+    #[prost(string, tag="17")]
+    pub amount1_decimal: ::prost::alloc::string::String,
+    /// Directional trade
+    #[prost(string, tag="18")]
+    pub token_in: ::prost::alloc::string::String,
+    #[prost(string, tag="19")]
+    pub token_out: ::prost::alloc::string::String,
+    #[prost(string, tag="20")]
+    pub amount_in: ::prost::alloc::string::String,
+    #[prost(string, tag="21")]
+    pub amount_out: ::prost::alloc::string::String,
+    #[prost(string, tag="22")]
+    pub amount_in_decimal: ::prost::alloc::string::String,
+    #[prost(string, tag="23")]
+    pub amount_out_decimal: ::prost::alloc::string::String,
+    /// Decimal-adjusted prices
+    ///
+    /// execution price of token0 in token1
+    #[prost(string, tag="24")]
+    pub price0: ::prost::alloc::string::String,
+    /// execution price of token1 in token0
+    #[prost(string, tag="25")]
+    pub price1: ::prost::alloc::string::String,
+    /// post-swap pool price of token0 in token1, from sqrtPriceX96
+    #[prost(string, tag="26")]
+    pub pool_price0: ::prost::alloc::string::String,
+    #[prost(uint64, tag="27")]
+    pub ordinal: u64,
+}
+/// Slipstream liquidity depth changes in a block
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ClLiquidityDepth {
+    #[prost(uint64, tag="1")]
+    pub block_number: u64,
+    #[prost(uint64, tag="2")]
+    pub timestamp: u64,
+    #[prost(message, repeated, tag="3")]
+    pub pools: ::prost::alloc::vec::Vec<ClPoolDepth>,
+}
+/// Current state and changed ticks of a Slipstream pool
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ClPoolDepth {
+    #[prost(string, tag="1")]
+    pub pool_address: ::prost::alloc::string::String,
+    /// active (in-range) liquidity
+    #[prost(string, tag="2")]
+    pub liquidity: ::prost::alloc::string::String,
+    #[prost(string, tag="3")]
+    pub sqrt_price_x96: ::prost::alloc::string::String,
+    #[prost(int32, tag="4")]
+    pub tick: i32,
+    /// ticks whose liquidityNet changed
+    #[prost(message, repeated, tag="5")]
+    pub ticks: ::prost::alloc::vec::Vec<ClTick>,
+}
+/// Net liquidity added when the price crosses a tick upwards
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ClTick {
+    #[prost(int32, tag="1")]
+    pub tick: i32,
+    #[prost(string, tag="2")]
+    pub liquidity_net: ::prost::alloc::string::String,
+}
+/// Slipstream position event collection (NonfungiblePositionManager)
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ClPositionEvents {
+    #[prost(message, repeated, tag="1")]
+    pub events: ::prost::alloc::vec::Vec<ClPositionEvent>,
+}
+/// Slipstream position change, paired with the pool event it triggered
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ClPositionEvent {
+    #[prost(uint64, tag="1")]
+    pub block_number: u64,
+    #[prost(string, tag="2")]
+    pub transaction_hash: ::prost::alloc::string::String,
+    #[prost(uint64, tag="3")]
+    pub log_index: u64,
+    #[prost(string, tag="4")]
+    pub token_id: ::prost::alloc::string::String,
+    /// "increase", "decrease", "collect" or "transfer"
+    #[prost(string, tag="5")]
+    pub action: ::prost::alloc::string::String,
+    /// empty for transfers
+    #[prost(string, tag="6")]
+    pub pool_address: ::prost::alloc::string::String,
+    #[prost(int32, tag="7")]
+    pub tick_lower: i32,
+    #[prost(int32, tag="8")]
+    pub tick_upper: i32,
+    #[prost(string, tag="9")]
+    pub liquidity: ::prost::alloc::string::String,
+    #[prost(string, tag="10")]
+    pub amount0: ::prost::alloc::string::String,
+    #[prost(string, tag="11")]
+    pub amount1: ::prost::alloc::string::String,
+    /// transfer sender
+    #[prost(string, tag="12")]
+    pub from: ::prost::alloc::string::String,
+    /// transfer receiver or collect recipient
+    #[prost(string, tag="13")]
+    pub to: ::prost::alloc::string::String,
+    #[prost(uint64, tag="14")]
+    pub timestamp: u64,
+    #[prost(string, tag="15")]
+    pub token0: ::prost::alloc::string::String,
+    #[prost(string, tag="16")]
+    pub token1: ::prost::alloc::string::String,
+}
+/// Slipstream LP position keyed by NFT token id
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ClPosition {
+    #[prost(string, tag="1")]
+    pub token_id: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub pool_address: ::prost::alloc::string::String,
+    #[prost(string, tag="3")]
+    pub token0: ::prost::alloc::string::String,
+    #[prost(string, tag="4")]
+    pub token1: ::prost::alloc::string::String,
+    #[prost(int32, tag="5")]
+    pub tick_lower: i32,
+    #[prost(int32, tag="6")]
+    pub tick_upper: i32,
+    #[prost(string, tag="7")]
+    pub owner: ::prost::alloc::string::String,
+    #[prost(string, tag="8")]
+    pub liquidity: ::prost::alloc::string::String,
+    /// Total collected, principal and fees
+    #[prost(string, tag="13")]
+    pub collected0: ::prost::alloc::string::String,
+    #[prost(string, tag="14")]
+    pub collected1: ::prost::alloc::string::String,
+    /// Total principal released by DecreaseLiquidity
+    #[prost(string, tag="15")]
+    pub withdrawn0: ::prost::alloc::string::String,
+    #[prost(string, tag="16")]
+    pub withdrawn1: ::prost::alloc::string::String,
+}
+/// VotingEscrow (veAERO) lock event collection
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct VeLockEvents {
+    #[prost(message, repeated, tag="1")]
+    pub events: ::prost::alloc::vec::Vec<VeLockEvent>,
+}
+/// Change to a single veAERO lock; Merge and Split emit one per token involved
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct VeLockEvent {
+    #[prost(uint64, tag="1")]
+    pub block_number: u64,
+    #[prost(string, tag="2")]
+    pub transaction_hash: ::prost::alloc::string::String,
+    #[prost(uint64, tag="3")]
+    pub log_index: u64,
+    #[prost(string, tag="4")]
+    pub token_id: ::prost::alloc::string::String,
+    /// "deposit", "withdraw", "merge", "split", "deposit_managed",
+    /// "withdraw_managed", "lock_permanent", "unlock_permanent" or "transfer"
+    #[prost(string, tag="5")]
+    pub action: ::prost::alloc::string::String,
+    /// provider, sender or lock owner
+    #[prost(string, tag="6")]
+    pub owner: ::prost::alloc::string::String,
+    /// signed change in locked AERO
+    #[prost(string, tag="7")]
+    pub amount: ::prost::alloc::string::String,
+    /// lock end after the event, 0 when permanent or withdrawn
+    #[prost(uint64, tag="8")]
+    pub unlock_time: u64,
+    /// Deposits only: "deposit_for", "create_lock", "increase_amount"
+    /// or "increase_unlock_time"
+    #[prost(string, tag="9")]
+    pub deposit_type: ::prost::alloc::string::String,
+    /// Other lock of a Merge or managed deposit/withdrawal, source lock of a Split
+    #[prost(string, tag="10")]
+    pub related_token_id: ::prost::alloc::string::String,
+    /// transfer sender
+    #[prost(string, tag="11")]
+    pub from: ::prost::alloc::string::String,
+    /// transfer receiver
+    #[prost(string, tag="12")]
+    pub to: ::prost::alloc::string::String,
+    #[prost(uint64, tag="13")]
+    pub timestamp: u64,
+}
+/// Current state of a veAERO lock keyed by NFT token id
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct VeLock {
+    #[prost(string, tag="1")]
+    pub token_id: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub owner: ::prost::alloc::string::String,
+    /// locked AERO
+    #[prost(string, tag="3")]
+    pub amount: ::prost::alloc::string::String,
+    #[prost(uint64, tag="4")]
+    pub unlock_time: u64,
+    #[prost(bool, tag="5")]
+    pub permanent: bool,
 }
 /// Sync event collection (reserve updates)
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -96,8 +435,112 @@ pub struct SyncEvent {
     pub reserve1: ::prost::alloc::string::String,
     #[prost(uint64, tag="5")]
     pub timestamp: u64,
+    #[prost(string, tag="6")]
+    pub transaction_hash: ::prost::alloc::string::String,
+    #[prost(uint64, tag="7")]
+    pub log_index: u64,
+    #[prost(uint64, tag="8")]
+    pub ordinal: u64,
+}
+/// Pool creation collection (PoolFactory)
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Pools {
+    #[prost(message, repeated, tag="1")]
+    pub pools: ::prost::alloc::vec::Vec<Pool>,
+}
+/// Aerodrome pool created by the PoolFactory
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Pool {
+    #[prost(string, tag="1")]
+    pub address: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub token0: ::prost::alloc::string::String,
+    #[prost(string, tag="3")]
+    pub token1: ::prost::alloc::string::String,
+    #[prost(bool, tag="4")]
+    pub stable: bool,
+    #[prost(uint64, tag="5")]
+    pub block_number: u64,
+    #[prost(string, tag="6")]
+    pub transaction_hash: ::prost::alloc::string::String,
+    #[prost(uint64, tag="7")]
+    pub timestamp: u64,
+    /// Slipstream (CLFactory) pools only
+    #[prost(int32, tag="8")]
+    pub tick_spacing: i32,
+    #[prost(uint64, tag="9")]
+    pub log_index: u64,
+    #[prost(uint64, tag="10")]
+    pub ordinal: u64,
+}
+/// ERC-20 token metadata resolved via eth_call
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Token {
+    #[prost(string, tag="1")]
+    pub address: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(string, tag="3")]
+    pub symbol: ::prost::alloc::string::String,
+    #[prost(uint64, tag="4")]
+    pub decimals: u64,
+}
+/// Token metadata collection
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Tokens {
+    #[prost(message, repeated, tag="1")]
+    pub tokens: ::prost::alloc::vec::Vec<Token>,
+}
+/// OHLCV candle collection
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Candles {
+    #[prost(message, repeated, tag="1")]
+    pub candles: ::prost::alloc::vec::Vec<Candle>,
 }
-/// Aerodrome Governance event
+/// OHLCV candle of token0 priced in token1
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Candle {
+    #[prost(string, tag="1")]
+    pub pool_address: ::prost::alloc::string::String,
+    #[prost(uint64, tag="2")]
+    pub interval_seconds: u64,
+    /// bucket start
+    #[prost(uint64, tag="3")]
+    pub timestamp: u64,
+    #[prost(string, tag="4")]
+    pub open: ::prost::alloc::string::String,
+    #[prost(string, tag="5")]
+    pub high: ::prost::alloc::string::String,
+    #[prost(string, tag="6")]
+    pub low: ::prost::alloc::string::String,
+    #[prost(string, tag="7")]
+    pub close: ::prost::alloc::string::String,
+    /// token0 traded (decimal-adjusted)
+    #[prost(string, tag="8")]
+    pub volume0: ::prost::alloc::string::String,
+    /// token1 traded (decimal-adjusted)
+    #[prost(string, tag="9")]
+    pub volume1: ::prost::alloc::string::String,
+    #[prost(uint64, tag="10")]
+    pub trade_count: u64,
+    /// a later candle has opened
+    #[prost(bool, tag="11")]
+    pub closed: bool,
+}
+/// Voter event collection
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GovernanceEvents {
+    #[prost(message, repeated, tag="1")]
+    pub events: ::prost::alloc::vec::Vec<AerodromeGovernance>,
+}
+/// Aerodrome Governance event (Voter votes and gauge lifecycle)
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct AerodromeGovernance {
@@ -107,17 +550,270 @@ pub struct AerodromeGovernance {
     pub transaction_hash: ::prost::alloc::string::String,
     #[prost(uint64, tag="3")]
     pub log_index: u64,
+    /// voter, gauge creator, whitelister or distributor
     #[prost(string, tag="4")]
     pub voter: ::prost::alloc::string::String,
     #[prost(string, tag="5")]
     pub delegate: ::prost::alloc::string::String,
+    /// vote weight or distributed AERO
     #[prost(string, tag="6")]
     pub amount: ::prost::alloc::string::String,
     #[prost(uint64, tag="7")]
     pub timestamp: u64,
-    /// "vote" or "delegate"
+    /// "vote", "abstain", "gauge_created", "gauge_killed", "gauge_revived",
+    /// "whitelist_token" or "distribute_reward"
+    #[prost(string, tag="8")]
+    pub action: ::prost::alloc::string::String,
+    #[prost(string, tag="9")]
+    pub pool_address: ::prost::alloc::string::String,
+    /// veAERO lock that voted
+    #[prost(string, tag="10")]
+    pub token_id: ::prost::alloc::string::String,
+    /// pool vote weight after a vote or abstain
+    #[prost(string, tag="11")]
+    pub pool_weight: ::prost::alloc::string::String,
+    #[prost(uint64, tag="12")]
+    pub epoch: u64,
+    #[prost(string, tag="13")]
+    pub gauge: ::prost::alloc::string::String,
+    #[prost(string, tag="14")]
+    pub bribe_voting_reward: ::prost::alloc::string::String,
+    #[prost(string, tag="15")]
+    pub fees_voting_reward: ::prost::alloc::string::String,
+    /// whitelisted token
+    #[prost(string, tag="16")]
+    pub token: ::prost::alloc::string::String,
+    #[prost(bool, tag="17")]
+    pub whitelisted: bool,
+}
+/// Gauge created by the Voter
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Gauge {
+    #[prost(string, tag="1")]
+    pub address: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub pool_address: ::prost::alloc::string::String,
+    #[prost(string, tag="3")]
+    pub bribe_voting_reward: ::prost::alloc::string::String,
+    #[prost(string, tag="4")]
+    pub fees_voting_reward: ::prost::alloc::string::String,
+    #[prost(uint64, tag="5")]
+    pub block_number: u64,
+    #[prost(uint64, tag="6")]
+    pub timestamp: u64,
+}
+/// Gauge staking and emission event collection
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GaugeEvents {
+    #[prost(message, repeated, tag="1")]
+    pub events: ::prost::alloc::vec::Vec<GaugeEvent>,
+}
+/// Individual Gauge event
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GaugeEvent {
+    #[prost(uint64, tag="1")]
+    pub block_number: u64,
+    #[prost(string, tag="2")]
+    pub transaction_hash: ::prost::alloc::string::String,
+    #[prost(uint64, tag="3")]
+    pub log_index: u64,
+    #[prost(string, tag="4")]
+    pub gauge: ::prost::alloc::string::String,
+    #[prost(string, tag="5")]
+    pub pool_address: ::prost::alloc::string::String,
+    /// "deposit", "withdraw", "claim_rewards" or "notify_reward"
+    #[prost(string, tag="6")]
+    pub action: ::prost::alloc::string::String,
+    /// staker, reward claimer or notifier
+    #[prost(string, tag="7")]
+    pub account: ::prost::alloc::string::String,
+    /// LP tokens staked/withdrawn or AERO claimed/notified
+    #[prost(string, tag="8")]
+    pub amount: ::prost::alloc::string::String,
+    #[prost(uint64, tag="9")]
+    pub epoch: u64,
+    #[prost(uint64, tag="10")]
+    pub timestamp: u64,
+}
+/// Emission and rebase event collection (Minter, RewardsDistributor)
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct EmissionEvents {
+    #[prost(message, repeated, tag="1")]
+    pub events: ::prost::alloc::vec::Vec<EmissionEvent>,
+}
+/// Individual Minter or RewardsDistributor event
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct EmissionEvent {
+    #[prost(uint64, tag="1")]
+    pub block_number: u64,
+    #[prost(string, tag="2")]
+    pub transaction_hash: ::prost::alloc::string::String,
+    #[prost(uint64, tag="3")]
+    pub log_index: u64,
+    /// "mint" (weekly emissions), "nudge" (tail emission rate change),
+    /// "checkpoint_token" (rebase added) or "claimed" (rebase claimed)
+    #[prost(string, tag="4")]
+    pub action: ::prost::alloc::string::String,
+    #[prost(uint64, tag="5")]
+    pub epoch: u64,
+    /// AERO emitted, rebased or claimed
+    #[prost(string, tag="6")]
+    pub amount: ::prost::alloc::string::String,
+    /// mint only
+    #[prost(string, tag="7")]
+    pub circulating_supply: ::prost::alloc::string::String,
+    /// mint only, tail emissions active
+    #[prost(bool, tag="8")]
+    pub tail: bool,
+    /// nudge only, in basis points
+    #[prost(string, tag="9")]
+    pub old_rate: ::prost::alloc::string::String,
+    /// nudge only, in basis points
+    #[prost(string, tag="10")]
+    pub new_rate: ::prost::alloc::string::String,
+    /// claimed only, veAERO lock
+    #[prost(string, tag="11")]
+    pub token_id: ::prost::alloc::string::String,
+    #[prost(uint64, tag="12")]
+    pub timestamp: u64,
+}
+/// Pool TVL changes in a block
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PoolTvlSnapshots {
+    #[prost(uint64, tag="1")]
+    pub block_number: u64,
+    #[prost(uint64, tag="2")]
+    pub timestamp: u64,
+    /// pools whose reserves changed
+    #[prost(message, repeated, tag="3")]
+    pub pools: ::prost::alloc::vec::Vec<PoolTvl>,
+    /// all priced V2-style pools, after the block
+    #[prost(string, tag="4")]
+    pub protocol_tvl_usd: ::prost::alloc::string::String,
+}
+/// Reserves and TVL of a V2-style pool
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PoolTvl {
+    #[prost(string, tag="1")]
+    pub pool_address: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub token0: ::prost::alloc::string::String,
+    #[prost(string, tag="3")]
+    pub token1: ::prost::alloc::string::String,
+    #[prost(string, tag="4")]
+    pub reserve0: ::prost::alloc::string::String,
+    #[prost(string, tag="5")]
+    pub reserve1: ::prost::alloc::string::String,
+    #[prost(string, tag="6")]
+    pub reserve0_decimal: ::prost::alloc::string::String,
+    #[prost(string, tag="7")]
+    pub reserve1_decimal: ::prost::alloc::string::String,
+    /// empty when neither token is priced
     #[prost(string, tag="8")]
+    pub tvl_usd: ::prost::alloc::string::String,
+}
+/// Daily and hourly pool snapshot collection
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PoolSnapshots {
+    #[prost(message, repeated, tag="1")]
+    pub snapshots: ::prost::alloc::vec::Vec<PoolSnapshot>,
+}
+/// Pool activity and reserves over a day or an hour
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PoolSnapshot {
+    #[prost(string, tag="1")]
+    pub pool_address: ::prost::alloc::string::String,
+    /// 86400 (day) or 3600 (hour)
+    #[prost(uint64, tag="2")]
+    pub interval_seconds: u64,
+    #[prost(uint64, tag="3")]
+    pub period_start: u64,
+    /// YYYY-MM-DD or YYYY-MM-DD-HH
+    #[prost(string, tag="4")]
+    pub bucket: ::prost::alloc::string::String,
+    /// raw units
+    #[prost(string, tag="5")]
+    pub open_reserve0: ::prost::alloc::string::String,
+    #[prost(string, tag="6")]
+    pub open_reserve1: ::prost::alloc::string::String,
+    #[prost(string, tag="7")]
+    pub close_reserve0: ::prost::alloc::string::String,
+    #[prost(string, tag="8")]
+    pub close_reserve1: ::prost::alloc::string::String,
+    #[prost(string, tag="10")]
+    pub volume_usd: ::prost::alloc::string::String,
+    /// raw units of token0
+    #[prost(string, tag="11")]
+    pub fees0: ::prost::alloc::string::String,
+    #[prost(string, tag="12")]
+    pub fees1: ::prost::alloc::string::String,
+    #[prost(uint64, tag="13")]
+    pub swap_count: u64,
+    #[prost(uint64, tag="14")]
+    pub unique_traders: u64,
+    /// empty until one of the pool's tokens is priced
+    #[prost(string, tag="15")]
+    pub tvl_usd: ::prost::alloc::string::String,
+    /// a later bucket has opened
+    #[prost(bool, tag="16")]
+    pub closed: bool,
+    /// token0 traded in or out (raw units)
+    #[prost(string, tag="17")]
+    pub volume0: ::prost::alloc::string::String,
+    /// token1 traded in or out (raw units)
+    #[prost(string, tag="18")]
+    pub volume1: ::prost::alloc::string::String,
+}
+/// Pool fee change collection (PoolFactory)
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PoolFeeEvents {
+    #[prost(message, repeated, tag="1")]
+    pub events: ::prost::alloc::vec::Vec<PoolFeeEvent>,
+}
+/// Pool creation or fee change on the PoolFactory
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PoolFeeEvent {
+    #[prost(uint64, tag="1")]
+    pub block_number: u64,
+    #[prost(string, tag="2")]
+    pub transaction_hash: ::prost::alloc::string::String,
+    /// call index for default_fee
+    #[prost(uint64, tag="3")]
+    pub log_index: u64,
+    #[prost(uint64, tag="4")]
+    pub ordinal: u64,
+    /// "pool_created", "custom_fee" (SetCustomFee) or "default_fee" (setFee call)
+    #[prost(string, tag="5")]
     pub action: ::prost::alloc::string::String,
+    /// empty for default_fee
+    #[prost(string, tag="6")]
+    pub pool_address: ::prost::alloc::string::String,
+    #[prost(bool, tag="7")]
+    pub stable: bool,
+    /// Fee set, in basis points; a custom fee of 0 falls back to the default
+    /// and 420 marks a zero-fee pool
+    #[prost(uint64, tag="8")]
+    pub fee: u64,
+    #[prost(uint64, tag="9")]
+    pub timestamp: u64,
+}
+/// Voting reward event collection (FeesVotingReward and BribeVotingReward)
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RewardEvents {
+    #[prost(message, repeated, tag="1")]
+    pub events: ::prost::alloc::vec::Vec<AerodromeReward>,
 }
 /// Aerodrome Reward event
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -129,6 +825,7 @@ pub struct AerodromeReward {
     pub transaction_hash: ::prost::alloc::string::String,
     #[prost(uint64, tag="3")]
     pub log_index: u64,
+    /// notifier or claimer
     #[prost(string, tag="4")]
     pub user: ::prost::alloc::string::String,
     #[prost(string, tag="5")]
@@ -137,5 +834,18 @@ pub struct AerodromeReward {
     pub amount: ::prost::alloc::string::String,
     #[prost(uint64, tag="7")]
     pub timestamp: u64,
+    /// "notify" or "claim"
+    #[prost(string, tag="8")]
+    pub action: ::prost::alloc::string::String,
+    /// "fees" or "bribe"
+    #[prost(string, tag="9")]
+    pub reward_type: ::prost::alloc::string::String,
+    #[prost(string, tag="10")]
+    pub reward_contract: ::prost::alloc::string::String,
+    #[prost(string, tag="11")]
+    pub pool_address: ::prost::alloc::string::String,
+    /// epoch the reward is for (notify) or claimed in (claim)
+    #[prost(uint64, tag="12")]
+    pub epoch: u64,
 }
 // @@protoc_insertion_point(module)
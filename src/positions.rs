@@ -0,0 +1,254 @@
+//! Slipstream LP positions
+//!
+//! Positions are NFTs minted by the NonfungiblePositionManager. Its
+//! `IncreaseLiquidity`, `DecreaseLiquidity` and `Collect` events only carry
+//! the token id, so each one is paired with the pool-level `Mint`, `Burn` or
+//! `Collect` the manager emitted just before it in the same transaction,
+//! which gives the pool and tick range.
+//!
+//! Position state is split across stores by update policy and assembled into
+//! `store_cl_positions`:
+//! - `store_cl_position_ranges` - pool, tokens and tick range (set_if_not_exists)
+//! - `store_cl_position_owners` - current NFT owner (set)
+//! - `store_cl_position_amounts` - liquidity and token flows (add)
+//!
+//! Uncollected fees are not tracked. `Collect` pays out withdrawn principal
+//! and fees together, so positions only carry their cumulative withdrawals
+//! and collections; fees would need the pool's feeGrowthInside.
+
+use crate::abi::cl_pool::events::{Burn as PoolBurn, Collect as PoolCollect, Mint as PoolMint};
+use crate::abi::nonfungible_position_manager::events::{
+    Collect, DecreaseLiquidity, IncreaseLiquidity, Transfer,
+};
+use crate::aerodrome_pool;
+use crate::math::parse_bigint;
+use crate::pb::aerodrome::{ClPosition, ClPositionEvent, ClPositionEvents, Pool};
+use hex_literal::hex;
+use std::collections::HashSet;
+use substreams::scalar::BigInt;
+use substreams::store::{
+    StoreAdd, StoreAddBigInt, StoreGet, StoreGetBigInt, StoreGetProto, StoreGetString, StoreNew,
+    StoreSet, StoreSetIfNotExists, StoreSetIfNotExistsProto, StoreSetProto, StoreSetString,
+};
+use substreams::Hex;
+use substreams_ethereum::pb::eth::v2 as eth;
+use substreams_ethereum::Event;
+
+/// Slipstream NonfungiblePositionManager on Base
+const POSITION_MANAGER: [u8; 20] = hex!("827922686190790b37229fd06084350e74485b72");
+
+/// Pool-level event awaiting the position manager event it belongs to
+struct PoolEvent {
+    pool: Pool,
+    tick_lower: i32,
+    tick_upper: i32,
+}
+
+/// Extract Slipstream position changes from the NonfungiblePositionManager
+#[substreams::handlers::map]
+pub fn map_cl_positions(
+    blk: eth::Block,
+    pools: StoreGetProto<Pool>,
+) -> Result<ClPositionEvents, substreams::errors::Error> {
+    let timestamp = blk
+        .header
+        .as_ref()
+        .and_then(|h| h.timestamp.as_ref())
+        .map(|t| t.seconds)
+        .unwrap_or(0);
+
+    let mut events = Vec::new();
+
+    for receipt in blk.receipts() {
+        let tx_hash = Hex(&receipt.transaction.hash).to_string();
+        // Latest pool event owned by the position manager in this transaction
+        let mut pending: Option<PoolEvent> = None;
+
+        for log in &receipt.receipt.logs {
+            if log.address != POSITION_MANAGER {
+                let ticks = if let Some(mint) = PoolMint::match_and_decode(log) {
+                    (mint.owner == POSITION_MANAGER)
+                        .then(|| (mint.tick_lower.to_i32(), mint.tick_upper.to_i32()))
+                } else if let Some(burn) = PoolBurn::match_and_decode(log) {
+                    (burn.owner == POSITION_MANAGER)
+                        .then(|| (burn.tick_lower.to_i32(), burn.tick_upper.to_i32()))
+                } else if let Some(collect) = PoolCollect::match_and_decode(log) {
+                    (collect.owner == POSITION_MANAGER)
+                        .then(|| (collect.tick_lower.to_i32(), collect.tick_upper.to_i32()))
+                } else {
+                    None
+                };
+                if let Some((tick_lower, tick_upper)) = ticks {
+                    if let Some(pool) = aerodrome_pool(&pools, &log.address) {
+                        pending = Some(PoolEvent {
+                            pool,
+                            tick_lower,
+                            tick_upper,
+                        });
+                    }
+                }
+                continue;
+            }
+
+            let mut event = ClPositionEvent {
+                block_number: blk.number,
+                transaction_hash: tx_hash.clone(),
+                log_index: log.index as u64,
+                timestamp: timestamp as u64,
+                ..Default::default()
+            };
+
+            if let Some(transfer) = Transfer::match_and_decode(log) {
+                event.token_id = transfer.token_id.to_string();
+                event.action = "transfer".to_string();
+                event.from = Hex(&transfer.from).to_string();
+                event.to = Hex(&transfer.to).to_string();
+                events.push(event);
+                continue;
+            }
+
+            if let Some(increase) = IncreaseLiquidity::match_and_decode(log) {
+                event.token_id = increase.token_id.to_string();
+                event.action = "increase".to_string();
+                event.liquidity = increase.liquidity.to_string();
+                event.amount0 = increase.amount0.to_string();
+                event.amount1 = increase.amount1.to_string();
+            } else if let Some(decrease) = DecreaseLiquidity::match_and_decode(log) {
+                event.token_id = decrease.token_id.to_string();
+                event.action = "decrease".to_string();
+                event.liquidity = decrease.liquidity.to_string();
+                event.amount0 = decrease.amount0.to_string();
+                event.amount1 = decrease.amount1.to_string();
+            } else if let Some(collect) = Collect::match_and_decode(log) {
+                event.token_id = collect.token_id.to_string();
+                event.action = "collect".to_string();
+                event.to = Hex(&collect.recipient).to_string();
+                event.amount0 = collect.amount0.to_string();
+                event.amount1 = collect.amount1.to_string();
+            } else {
+                continue;
+            }
+
+            // Liquidity changes on pools we do not track are skipped
+            let Some(paired) = pending.take() else {
+                continue;
+            };
+            event.pool_address = paired.pool.address;
+            event.token0 = paired.pool.token0;
+            event.token1 = paired.pool.token1;
+            event.tick_lower = paired.tick_lower;
+            event.tick_upper = paired.tick_upper;
+            events.push(event);
+        }
+    }
+
+    Ok(ClPositionEvents { events })
+}
+
+/// Store handler for the pool, tokens and tick range of each position
+///
+/// Key formats:
+/// - `position:{token_id}` - ClPosition with pool, tokens and ticks only
+#[substreams::handlers::store]
+pub fn store_cl_position_ranges(
+    events: ClPositionEvents,
+    store: StoreSetIfNotExistsProto<ClPosition>,
+) {
+    for event in &events.events {
+        if event.pool_address.is_empty() {
+            continue;
+        }
+        let range = ClPosition {
+            token_id: event.token_id.clone(),
+            pool_address: event.pool_address.clone(),
+            token0: event.token0.clone(),
+            token1: event.token1.clone(),
+            tick_lower: event.tick_lower,
+            tick_upper: event.tick_upper,
+            ..Default::default()
+        };
+        store.set_if_not_exists(0, format!("position:{}", event.token_id), &range);
+    }
+}
+
+/// Store handler for the current owner of each position NFT
+///
+/// Key formats:
+/// - `position:{token_id}` - Owner address (zero address once burned)
+#[substreams::handlers::store]
+pub fn store_cl_position_owners(events: ClPositionEvents, store: StoreSetString) {
+    for event in &events.events {
+        if event.action == "transfer" {
+            store.set(0, format!("position:{}", event.token_id), &event.to);
+        }
+    }
+}
+
+/// Store handler for position liquidity and token flows
+///
+/// Key formats:
+/// - `position:{token_id}:liquidity` - Current liquidity
+/// - `position:{token_id}:withdrawn{0,1}` - Principal released by DecreaseLiquidity
+/// - `position:{token_id}:collected{0,1}` - Tokens collected (principal and fees)
+#[substreams::handlers::store]
+pub fn store_cl_position_amounts(events: ClPositionEvents, store: StoreAddBigInt) {
+    for event in &events.events {
+        let key = format!("position:{}", event.token_id);
+        let liquidity = parse_bigint(&event.liquidity);
+
+        match event.action.as_str() {
+            "increase" => {
+                store.add(0, format!("{}:liquidity", key), &liquidity);
+            }
+            "decrease" => {
+                store.add(0, format!("{}:liquidity", key), liquidity.neg());
+                store.add(0, format!("{}:withdrawn0", key), parse_bigint(&event.amount0));
+                store.add(0, format!("{}:withdrawn1", key), parse_bigint(&event.amount1));
+            }
+            "collect" => {
+                store.add(0, format!("{}:collected0", key), parse_bigint(&event.amount0));
+                store.add(0, format!("{}:collected1", key), parse_bigint(&event.amount1));
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Store handler assembling the full state of every position touched in the block
+///
+/// Key formats:
+/// - `position:{token_id}` - ClPosition with owner, tick range, liquidity and token flows
+#[substreams::handlers::store]
+pub fn store_cl_positions(
+    events: ClPositionEvents,
+    ranges: StoreGetProto<ClPosition>,
+    owners: StoreGetString,
+    amounts: StoreGetBigInt,
+    store: StoreSetProto<ClPosition>,
+) {
+    let mut seen = HashSet::new();
+
+    for event in &events.events {
+        if !seen.insert(event.token_id.as_str()) {
+            continue;
+        }
+        let key = format!("position:{}", event.token_id);
+        // Positions minted on untracked pools have no range
+        let Some(mut position) = ranges.get_last(&key) else {
+            continue;
+        };
+        let amount = |name: &str| {
+            amounts
+                .get_last(format!("{}:{}", key, name))
+                .unwrap_or_else(BigInt::zero)
+        };
+
+        position.owner = owners.get_last(&key).unwrap_or_default();
+        position.liquidity = amount("liquidity").to_string();
+        position.collected0 = amount("collected0").to_string();
+        position.collected1 = amount("collected1").to_string();
+        position.withdrawn0 = amount("withdrawn0").to_string();
+        position.withdrawn1 = amount("withdrawn1").to_string();
+        store.set(0, &key, &position);
+    }
+}
@@ -0,0 +1,133 @@
+//! Pool price math
+//!
+//! Prices are decimal-adjusted, i.e. computed from amounts and reserves
+//! already scaled down by their token decimals. Volatile pools follow the
+//! constant product `x * y = k`; stable pools follow Aerodrome's
+//! `x^3 * y + y^3 * x = k` curve, whose marginal price is
+//! `(3x^2y + y^3) / (x^3 + 3xy^2)`.
+
+use substreams::scalar::{BigDecimal, BigInt};
+
+/// Significant digits kept for every emitted price
+const PRICE_PRECISION: u64 = 38;
+
+/// Spot price of token0 in token1 for reserves `x` (token0) and `y` (token1)
+pub fn mid_price0(x: &BigDecimal, y: &BigDecimal, stable: bool) -> Option<BigDecimal> {
+    if x.is_zero() || y.is_zero() {
+        return None;
+    }
+
+    let price = if stable {
+        let x2 = x.clone() * x.clone();
+        let y2 = y.clone() * y.clone();
+        let numerator = BigDecimal::from(3) * x2.clone() * y.clone() + y2.clone() * y.clone();
+        let denominator = x2 * x.clone() + BigDecimal::from(3) * x.clone() * y2;
+        numerator / denominator
+    } else {
+        y.clone() / x.clone()
+    };
+
+    Some(round(price))
+}
+
+/// Price of token0 in token1 from a Slipstream `sqrtPriceX96`
+///
+/// `price = (sqrtPriceX96 / 2^96)^2`, scaled by `10^(decimals0 - decimals1)`.
+pub fn sqrt_price_x96_to_price0(
+    sqrt_price_x96: &BigInt,
+    decimals0: u64,
+    decimals1: u64,
+) -> Option<BigDecimal> {
+    if sqrt_price_x96.is_zero() {
+        return None;
+    }
+    let numerator = BigDecimal::new(
+        sqrt_price_x96.clone() * sqrt_price_x96.clone(),
+        decimals0 as i64 - decimals1 as i64,
+    );
+    let denominator = BigDecimal::from(BigInt::from(1u64) << 192u32);
+    Some(round(numerator / denominator))
+}
+
+/// Price of the base amount expressed in the quote token
+pub fn ratio(quote: &BigDecimal, base: &BigDecimal) -> Option<BigDecimal> {
+    if base.is_zero() || quote.is_zero() {
+        return None;
+    }
+    Some(round(quote.clone() / base.clone()))
+}
+
+/// Relative shortfall of the execution price versus the pre-swap mid-price
+///
+/// Both prices must be quoted in the same direction (token_out per
+/// token_in); the result includes the pool fee.
+pub fn price_impact(execution: &BigDecimal, mid: &BigDecimal) -> Option<BigDecimal> {
+    if mid.is_zero() {
+        return None;
+    }
+    Some(round(BigDecimal::one() - execution.clone() / mid.clone()))
+}
+
+/// Cap a quotient at `PRICE_PRECISION` significant digits without padding exact values
+pub fn round(value: BigDecimal) -> BigDecimal {
+    if value.digits() > PRICE_PRECISION {
+        value.with_prec(PRICE_PRECISION)
+    } else {
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dec(value: &str) -> BigDecimal {
+        value.parse().unwrap()
+    }
+
+    #[test]
+    fn test_volatile_mid_price() {
+        // 10 WETH against 30,000 USDC
+        let price = mid_price0(&dec("10"), &dec("30000"), false).unwrap();
+        assert_eq!(price, dec("3000"));
+    }
+
+    #[test]
+    fn test_stable_mid_price_balanced() {
+        // A balanced stable pool trades at parity
+        let price = mid_price0(&dec("1000000"), &dec("1000000"), true).unwrap();
+        assert_eq!(price, dec("1"));
+    }
+
+    #[test]
+    fn test_stable_mid_price_flatter_than_volatile() {
+        let (x, y) = (dec("1100000"), dec("900000"));
+        let stable = mid_price0(&x, &y, true).unwrap();
+        let volatile = mid_price0(&x, &y, false).unwrap();
+        // The stable curve keeps the price closer to 1 than x*y for the same imbalance
+        assert!(stable < dec("1"));
+        assert!(stable > volatile);
+    }
+
+    #[test]
+    fn test_empty_reserves() {
+        assert!(mid_price0(&dec("0"), &dec("1"), false).is_none());
+    }
+
+    #[test]
+    fn test_sqrt_price_x96() {
+        // sqrtPriceX96 = 2^96 is a raw price of exactly 1
+        let one = BigInt::from(1u64) << 96u32;
+        assert_eq!(sqrt_price_x96_to_price0(&one, 18, 18).unwrap(), dec("1"));
+        // WETH (18) / USDC (6) at a raw price of 1e-9 reads as 1000 USDC per WETH
+        let sqrt: BigInt = "2505414483750479468691456".parse().unwrap(); // sqrt(1e-9) * 2^96
+        let price = sqrt_price_x96_to_price0(&sqrt, 18, 6).unwrap();
+        assert!(price > dec("999.99") && price < dec("1000.01"));
+    }
+
+    #[test]
+    fn test_price_impact() {
+        let impact = price_impact(&dec("2970"), &dec("3000")).unwrap();
+        assert_eq!(impact, dec("0.01"));
+    }
+}
@@ -0,0 +1,219 @@
+//! USD token prices derived from on-chain pools
+//!
+//! USDC and USDbC are taken at $1. WETH is priced from its deepest pool
+//! against them, and every other token from its deepest pool against USDC,
+//! USDbC or WETH. A pool's depth is the USD value of its quote-side reserve;
+//! pools below `MIN_QUOTE_LIQUIDITY_USD` are too thin to set a price.
+//!
+//! Prices are updated at the ordinal of every Sync of a pricing pool, so a
+//! move in the WETH price reaches WETH-quoted tokens on their next Sync.
+
+use crate::pb::aerodrome::{Pool, Pools, SyncEvents, Token};
+use crate::{pool_reserves, prices, token_decimals};
+use substreams::scalar::BigDecimal;
+use substreams::store::{
+    Appender, StoreAppend, StoreGet, StoreGetArray, StoreGetBigDecimal, StoreGetBigInt,
+    StoreGetProto, StoreNew, StoreSet, StoreSetBigDecimal,
+};
+
+/// USDC on Base
+const USDC: &str = "833589fcd6edb6e08f4c7c32d4f71b54bda02913";
+
+/// USDbC (bridged USDC) on Base
+const USDBC: &str = "d9aaec86b65d86f6a7b5b1b0c42ffa531710b6ca";
+
+/// WETH on Base
+const WETH: &str = "4200000000000000000000000000000000000006";
+
+/// Minimum USD value of a pool's quote-side reserve for it to price a token
+const MIN_QUOTE_LIQUIDITY_USD: u64 = 10_000;
+
+/// Store handler for the pools each token can be priced from
+///
+/// Key formats:
+/// - `token:{address}` - Pools pairing the token with USDC, USDbC or WETH
+#[substreams::handlers::store]
+pub fn store_pricing_pools(pools: Pools, store: StoreAppend<String>) {
+    for pool in &pools.pools {
+        for (token, quote) in [(&pool.token0, &pool.token1), (&pool.token1, &pool.token0)] {
+            if is_quote_for(token, quote) {
+                store.append(0, format!("token:{}", token), pool.address.clone());
+            }
+        }
+    }
+}
+
+/// Store handler for token USD prices
+///
+/// Key formats:
+/// - `token:{address}` - USD price, set at the ordinal of the Sync that moved it
+#[substreams::handlers::store]
+pub fn store_token_prices(
+    syncs: SyncEvents,
+    pools: StoreGetProto<Pool>,
+    tokens: StoreGetProto<Token>,
+    pricing_pools: StoreGetArray<String>,
+    reserves: StoreGetBigInt,
+    store: StoreSetBigDecimal,
+) {
+    let sources = PriceSources {
+        pools: &pools,
+        tokens: &tokens,
+        pricing_pools: &pricing_pools,
+        reserves: &reserves,
+    };
+
+    for sync in &syncs.events {
+        let Some(pool) = pools.get_last(format!("pool:{}", sync.pool_address)) else {
+            continue;
+        };
+        for (token, quote) in [(&pool.token0, &pool.token1), (&pool.token1, &pool.token0)] {
+            if !is_quote_for(token, quote) {
+                continue;
+            }
+            if let Some(price) = sources.price(sync.ordinal, token) {
+                store.set(sync.ordinal, format!("token:{}", token), &price);
+            }
+        }
+    }
+}
+
+/// USD price of a token as of `ordinal`, `None` until a deep enough pool prices it
+pub(crate) fn usd_price(
+    prices: &StoreGetBigDecimal,
+    ordinal: u64,
+    token: &str,
+) -> Option<BigDecimal> {
+    if is_stablecoin(token) {
+        return Some(BigDecimal::one());
+    }
+    prices.get_at(ordinal, format!("token:{}", token))
+}
+
+/// USD value of a swap, averaging both legs when both tokens are priced
+pub(crate) fn swap_value_usd(
+    amount_in: &BigDecimal,
+    price_in: Option<BigDecimal>,
+    amount_out: &BigDecimal,
+    price_out: Option<BigDecimal>,
+) -> Option<BigDecimal> {
+    let value = match (price_in, price_out) {
+        (Some(price_in), Some(price_out)) => {
+            (amount_in.clone() * price_in + amount_out.clone() * price_out) / BigDecimal::from(2)
+        }
+        (Some(price_in), None) => amount_in.clone() * price_in,
+        (None, Some(price_out)) => amount_out.clone() * price_out,
+        (None, None) => return None,
+    };
+    Some(prices::round(value))
+}
+
+/// Stores a token price is derived from
+struct PriceSources<'a> {
+    pools: &'a StoreGetProto<Pool>,
+    tokens: &'a StoreGetProto<Token>,
+    pricing_pools: &'a StoreGetArray<String>,
+    reserves: &'a StoreGetBigInt,
+}
+
+impl PriceSources<'_> {
+    /// USD price of a token as of `ordinal`, from its deepest pricing pool
+    fn price(&self, ordinal: u64, token: &str) -> Option<BigDecimal> {
+        if is_stablecoin(token) {
+            return Some(BigDecimal::one());
+        }
+        let min_depth = BigDecimal::from(MIN_QUOTE_LIQUIDITY_USD);
+
+        // (depth, price) of the deepest pool so far
+        let mut best: Option<(BigDecimal, BigDecimal)> = None;
+        for address in self.pricing_pools.get_last(format!("token:{}", token))? {
+            let Some(pool) = self.pools.get_last(format!("pool:{}", address)) else {
+                continue;
+            };
+            let token_is_0 = pool.token0 == token;
+            let quote = if token_is_0 {
+                &pool.token1
+            } else {
+                &pool.token0
+            };
+            // WETH-quoted pools recurse once, WETH itself is only quoted in stablecoins
+            let Some(quote_price) = self.price(ordinal, quote) else {
+                continue;
+            };
+
+            let decimals0 = token_decimals(self.tokens, &pool.token0);
+            let decimals1 = token_decimals(self.tokens, &pool.token1);
+            let Some((reserve0, reserve1)) =
+                pool_reserves(self.reserves, ordinal, &pool.address, decimals0, decimals1)
+            else {
+                continue;
+            };
+            let (token_reserve, quote_reserve) = if token_is_0 {
+                (reserve0, reserve1)
+            } else {
+                (reserve1, reserve0)
+            };
+
+            let depth = quote_reserve.clone() * quote_price.clone();
+            if depth < min_depth || best.as_ref().is_some_and(|(best, _)| *best >= depth) {
+                continue;
+            }
+            let Some(price) = prices::mid_price0(&token_reserve, &quote_reserve, pool.stable)
+            else {
+                continue;
+            };
+            best = Some((depth, price * quote_price));
+        }
+
+        best.map(|(_, price)| prices::round(price))
+    }
+}
+
+/// Stablecoins taken at $1
+fn is_stablecoin(token: &str) -> bool {
+    token == USDC || token == USDBC
+}
+
+/// Whether a pool pairing `token` with `quote` can price `token`
+fn is_quote_for(token: &str, quote: &str) -> bool {
+    !is_stablecoin(token) && (is_stablecoin(quote) || quote == WETH)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    const AERO: &str = "940181a94a35a4569e4529a3cdfb74e38fd98631";
+
+    #[test]
+    fn test_is_quote_for() {
+        assert!(is_quote_for(WETH, USDC));
+        assert!(is_quote_for(AERO, WETH));
+        assert!(is_quote_for(AERO, USDBC));
+        assert!(!is_quote_for(USDC, WETH));
+        assert!(!is_quote_for(USDBC, USDC));
+        assert!(!is_quote_for(WETH, AERO));
+    }
+
+    #[test]
+    fn test_swap_value_usd() {
+        let amount_in = BigDecimal::from_str("2").unwrap();
+        let amount_out = BigDecimal::from_str("5000").unwrap();
+        let weth = BigDecimal::from_str("2510").unwrap();
+
+        // Both legs priced: the average of 5020 and 5000
+        let value = swap_value_usd(
+            &amount_in,
+            Some(weth.clone()),
+            &amount_out,
+            Some(BigDecimal::one()),
+        );
+        assert_eq!(value, Some(BigDecimal::from(5010)));
+
+        let value = swap_value_usd(&amount_in, Some(weth), &amount_out, None);
+        assert_eq!(value, Some(BigDecimal::from(5020)));
+
+        assert_eq!(swap_value_usd(&amount_in, None, &amount_out, None), None);
+    }
+}
@@ -0,0 +1,139 @@
+//! Voting rewards (fees and bribes)
+//!
+//! Every gauge has a FeesVotingReward, funded with the pool's trading fees,
+//! and a BribeVotingReward, funded by anyone. Rewards notified during an
+//! epoch are paid to the locks that voted for the pool in that epoch.
+
+use crate::abi::voting_reward::events::{ClaimRewards, NotifyReward};
+use crate::calendar::{epoch_number, WEEK};
+use crate::math::parse_bigint;
+use crate::pb::aerodrome::{AerodromeReward, Gauge, RewardEvents};
+use substreams::store::{StoreAdd, StoreAddBigInt, StoreGet, StoreGetProto, StoreNew};
+use substreams::Hex;
+use substreams_ethereum::pb::eth::v2 as eth;
+use substreams_ethereum::Event;
+
+/// Extract reward notifications and claims from gauge voting reward contracts
+#[substreams::handlers::map]
+pub fn map_voting_rewards(
+    blk: eth::Block,
+    gauges: StoreGetProto<Gauge>,
+) -> Result<RewardEvents, substreams::errors::Error> {
+    let timestamp = blk
+        .header
+        .as_ref()
+        .and_then(|h| h.timestamp.as_ref())
+        .map(|t| t.seconds)
+        .unwrap_or(0) as u64;
+
+    let mut events = Vec::new();
+
+    for receipt in blk.receipts() {
+        let tx_hash = Hex(&receipt.transaction.hash).to_string();
+
+        for log in &receipt.receipt.logs {
+            // NotifyReward carries the epoch start the reward is for
+            let (action, user, token, amount, epoch) =
+                if let Some(notify) = NotifyReward::match_and_decode(log) {
+                    let epoch = notify.epoch.to_u64() / WEEK;
+                    ("notify", notify.from, notify.reward, notify.amount, epoch)
+                } else if let Some(claim) = ClaimRewards::match_and_decode(log) {
+                    let epoch = epoch_number(timestamp);
+                    ("claim", claim.from, claim.reward, claim.amount, epoch)
+                } else {
+                    continue;
+                };
+
+            let reward_contract = Hex(&log.address).to_string();
+            let Some(gauge) = gauges.get_last(format!("reward:{}", reward_contract)) else {
+                continue;
+            };
+            let reward_type = if gauge.fees_voting_reward == reward_contract {
+                "fees"
+            } else {
+                "bribe"
+            };
+
+            events.push(AerodromeReward {
+                block_number: blk.number,
+                transaction_hash: tx_hash.clone(),
+                log_index: log.index as u64,
+                user: Hex(&user).to_string(),
+                token: Hex(&token).to_string(),
+                amount: amount.to_string(),
+                timestamp,
+                action: action.to_string(),
+                reward_type: reward_type.to_string(),
+                reward_contract,
+                pool_address: gauge.pool_address,
+                epoch,
+            });
+        }
+    }
+
+    Ok(RewardEvents { events })
+}
+
+/// Store handler for voting rewards per pool, reward token and epoch
+///
+/// Key formats:
+/// - `epoch:{n}:pool:{address}:{fees|bribe}:{token}:notified` - Rewards added for the epoch
+/// - `epoch:{n}:pool:{address}:{fees|bribe}:{token}:claimed` - Rewards claimed during the epoch
+#[substreams::handlers::store]
+pub fn store_voting_rewards(events: RewardEvents, store: StoreAddBigInt) {
+    for event in &events.events {
+        let column = match event.action.as_str() {
+            "notify" => "notified",
+            "claim" => "claimed",
+            _ => continue,
+        };
+        store.add(
+            0,
+            format!(
+                "epoch:{}:pool:{}:{}:{}:{}",
+                event.epoch, event.pool_address, event.reward_type, event.token, column
+            ),
+            &parse_bigint(&event.amount),
+        );
+    }
+}
+
+/// Voting reward row of a `store_voting_rewards` key
+pub(crate) struct RewardKey<'a> {
+    pub epoch: u64,
+    pub pool: &'a str,
+    pub reward_type: &'a str,
+    pub token: &'a str,
+    pub column: &'a str,
+}
+
+/// Split an `epoch:{n}:pool:{address}:{type}:{token}:{column}` key
+pub(crate) fn parse_reward_key(key: &str) -> Option<RewardKey<'_>> {
+    let parts: Vec<&str> = key.split(':').collect();
+    match parts.as_slice() {
+        ["epoch", epoch, "pool", pool, reward_type, token, column] => Some(RewardKey {
+            epoch: epoch.parse().ok()?,
+            pool,
+            reward_type,
+            token,
+            column,
+        }),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_reward_key() {
+        let key = parse_reward_key("epoch:2818:pool:abc:bribe:def:notified").unwrap();
+        assert_eq!(key.epoch, 2818);
+        assert_eq!(key.pool, "abc");
+        assert_eq!(key.reward_type, "bribe");
+        assert_eq!(key.token, "def");
+        assert_eq!(key.column, "notified");
+        assert!(parse_reward_key("epoch:2818:pool:abc").is_none());
+    }
+}
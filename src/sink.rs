@@ -0,0 +1,213 @@
+//! Helpers mapping store state onto the SQL sink tables
+//!
+//! Aggregates are computed by the stores (add/max/min/set_if_not_exists) and
+//! `db_out` upserts the resulting totals from the store deltas, so repeated
+//! updates within a block collapse into a single row change.
+
+/// Row and column of an aggregate table fed by a `store_swap_volumes` key
+///
+/// Returns `(table, primary key, column)`, or `None` for keys that are not
+/// sunk to the aggregate tables.
+pub fn stats_column(key: &str) -> Option<(&'static str, &str, &'static str)> {
+    let mut parts = key.split(':');
+    let scope = parts.next()?;
+
+    let (table, id, metric) = match scope {
+        "total" => ("protocol_metrics", "aerodrome", parts.next()?),
+        "pool" => ("pool_stats", parts.next()?, parts.next()?),
+        "trader" => ("trader_stats", parts.next()?, parts.next()?),
+        "daily" => ("daily_stats", parts.next()?, parts.next()?),
+        "hourly" => ("hourly_stats", parts.next()?, parts.next()?),
+        "weekly" => ("weekly_stats", parts.next()?, parts.next()?),
+        "monthly" => ("monthly_stats", parts.next()?, parts.next()?),
+        "epoch" => ("epoch_stats", parts.next()?, parts.next()?),
+        _ => return None,
+    };
+    if parts.next().is_some() {
+        return None;
+    }
+
+    let column = match (table, metric) {
+        ("protocol_metrics", "swaps") | ("trader_stats", "count") => "total_swaps",
+        (_, "count") => "swap_count",
+        (_, "volume0") => "total_volume0",
+        (_, "volume1") => "total_volume1",
+        (_, "volume_usd") => "total_volume_usd",
+        ("pool_stats", "fees0") => "total_fees0",
+        ("pool_stats", "fees1") => "total_fees1",
+        _ => return None,
+    };
+    Some((table, id, column))
+}
+
+/// Per-pool row of a time bucket fed by a `store_swap_volumes` key
+///
+/// Maps `epoch:{n}:pool:{address}:{metric}` onto `pool_epoch_stats` and
+/// `daily:{date}:pool:{address}:{metric}` onto `pool_daily_stats`, returning
+/// `(table, bucket, pool, column)`.
+pub fn pool_bucket_column(key: &str) -> Option<(&'static str, &str, &str, &'static str)> {
+    let parts: Vec<&str> = key.split(':').collect();
+    let [scope, bucket, "pool", pool, metric] = parts.as_slice() else {
+        return None;
+    };
+    let table = match *scope {
+        "epoch" => "pool_epoch_stats",
+        "daily" => "pool_daily_stats",
+        _ => return None,
+    };
+    let column = match *metric {
+        "count" => "swap_count",
+        "volume0" => "total_volume0",
+        "volume1" => "total_volume1",
+        "volume_usd" => "total_volume_usd",
+        "fees0" => "total_fees0",
+        "fees1" => "total_fees1",
+        _ => return None,
+    };
+    Some((table, bucket, pool, column))
+}
+
+/// Row and column of an aggregate table fed by a `store_trader_counts` key
+///
+/// Returns `(table, primary key, column)`. Per-pool bucket counts only feed
+/// `map_pool_snapshots` and map to `None`.
+pub fn trader_count_column(key: &str) -> Option<(&'static str, &str, &'static str)> {
+    let parts: Vec<&str> = key.split(':').collect();
+    let row = match parts.as_slice() {
+        ["total"] => ("protocol_metrics", "aerodrome", "total_traders"),
+        ["pool", pool] => ("pool_stats", *pool, "unique_traders"),
+        ["daily", date] => ("daily_stats", *date, "unique_traders"),
+        ["daily", date, "new"] => ("daily_stats", *date, "new_traders"),
+        ["daily", date, "returning"] => ("daily_stats", *date, "returning_traders"),
+        ["weekly", week] => ("weekly_stats", *week, "unique_traders"),
+        ["monthly", month] => ("monthly_stats", *month, "unique_traders"),
+        ["epoch", epoch] => ("epoch_stats", *epoch, "unique_traders"),
+        _ => return None,
+    };
+    Some(row)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats_column() {
+        assert_eq!(
+            stats_column("pool:cdac0d6c:volume0"),
+            Some(("pool_stats", "cdac0d6c", "total_volume0"))
+        );
+        assert_eq!(
+            stats_column("daily:2024-01-15:count"),
+            Some(("daily_stats", "2024-01-15", "swap_count"))
+        );
+        assert_eq!(
+            stats_column("hourly:2024-01-15-13:volume_usd"),
+            Some(("hourly_stats", "2024-01-15-13", "total_volume_usd"))
+        );
+        assert_eq!(
+            stats_column("trader:cf77a3ba:count"),
+            Some(("trader_stats", "cf77a3ba", "total_swaps"))
+        );
+        assert_eq!(
+            stats_column("total:swaps"),
+            Some(("protocol_metrics", "aerodrome", "total_swaps"))
+        );
+    }
+
+    #[test]
+    fn test_stats_column_calendar_buckets() {
+        assert_eq!(
+            stats_column("weekly:2024-W03:volume_usd"),
+            Some(("weekly_stats", "2024-W03", "total_volume_usd"))
+        );
+        assert_eq!(
+            stats_column("monthly:2024-01:count"),
+            Some(("monthly_stats", "2024-01", "swap_count"))
+        );
+        assert_eq!(
+            stats_column("epoch:2818:count"),
+            Some(("epoch_stats", "2818", "swap_count"))
+        );
+        assert_eq!(stats_column("epoch:2818:pool:cdac0d6c:volume0"), None);
+    }
+
+    #[test]
+    fn test_stats_column_pool_fees() {
+        assert_eq!(
+            stats_column("pool:cdac0d6c:fees0"),
+            Some(("pool_stats", "cdac0d6c", "total_fees0"))
+        );
+        assert_eq!(stats_column("daily:2024-01-15:fees0"), None);
+    }
+
+    #[test]
+    fn test_usd_volume_columns() {
+        assert_eq!(
+            stats_column("total:volume_usd"),
+            Some(("protocol_metrics", "aerodrome", "total_volume_usd"))
+        );
+        assert_eq!(
+            stats_column("trader:cf77a3ba:volume_usd"),
+            Some(("trader_stats", "cf77a3ba", "total_volume_usd"))
+        );
+        assert_eq!(
+            pool_bucket_column("epoch:2818:pool:cdac0d6c:volume_usd"),
+            Some(("pool_epoch_stats", "2818", "cdac0d6c", "total_volume_usd"))
+        );
+    }
+
+    #[test]
+    fn test_pool_bucket_column() {
+        assert_eq!(
+            pool_bucket_column("epoch:2818:pool:cdac0d6c:count"),
+            Some(("pool_epoch_stats", "2818", "cdac0d6c", "swap_count"))
+        );
+        assert_eq!(
+            pool_bucket_column("daily:2024-01-15:pool:cdac0d6c:fees1"),
+            Some(("pool_daily_stats", "2024-01-15", "cdac0d6c", "total_fees1"))
+        );
+        assert_eq!(
+            pool_bucket_column("daily:2024-01-15:pool:cdac0d6c:volume1"),
+            Some((
+                "pool_daily_stats",
+                "2024-01-15",
+                "cdac0d6c",
+                "total_volume1"
+            ))
+        );
+        assert_eq!(pool_bucket_column("epoch:2818:count"), None);
+        assert_eq!(
+            pool_bucket_column("hourly:2024-01-15-13:pool:cdac0d6c:fees0"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_trader_count_column() {
+        assert_eq!(
+            trader_count_column("total"),
+            Some(("protocol_metrics", "aerodrome", "total_traders"))
+        );
+        assert_eq!(
+            trader_count_column("daily:2024-01-15"),
+            Some(("daily_stats", "2024-01-15", "unique_traders"))
+        );
+        assert_eq!(
+            trader_count_column("daily:2024-01-15:returning"),
+            Some(("daily_stats", "2024-01-15", "returning_traders"))
+        );
+        assert_eq!(
+            trader_count_column("pool:cdac0d6c"),
+            Some(("pool_stats", "cdac0d6c", "unique_traders"))
+        );
+        assert_eq!(trader_count_column("daily:2024-01-15:pool:cdac0d6c"), None);
+    }
+
+    #[test]
+    fn test_stats_column_ignores_other_keys() {
+        assert_eq!(stats_column("pool:cdac0d6c:trade_count"), None);
+        assert_eq!(stats_column("pool:cdac0d6c:trader:cf77a3ba"), None);
+        assert_eq!(stats_column("unknown:key"), None);
+    }
+}
@@ -0,0 +1,150 @@
+//! Slipstream (concentrated liquidity) pools
+//!
+//! Slipstream pools are deployed by the CLFactory and report swaps as signed
+//! pool balance deltas together with the post-swap `sqrtPriceX96`, active
+//! liquidity and tick, Uniswap V3 style.
+
+use crate::abi::cl_factory::events::PoolCreated;
+use crate::abi::cl_pool::events::Swap;
+use crate::math::to_decimal;
+use crate::pb::aerodrome::{AerodromeClSwap, ClSwapEvents, Pool, Pools, Token};
+use crate::prices;
+use crate::swaps::SwapDirection;
+use crate::{aerodrome_pool, decimal_string, token_decimals};
+use hex_literal::hex;
+use substreams::store::{StoreGet, StoreGetProto};
+use substreams::Hex;
+use substreams_ethereum::pb::eth::v2 as eth;
+use substreams_ethereum::Event;
+
+/// Slipstream CLFactory on Base
+const CL_FACTORY: [u8; 20] = hex!("5e7bb104d84c7cb9b682aac2f3d509f5f406809a");
+
+/// Extract pools created by the Slipstream CLFactory
+#[substreams::handlers::map]
+pub fn map_cl_pools_created(blk: eth::Block) -> Result<Pools, substreams::errors::Error> {
+    let timestamp = blk
+        .header
+        .as_ref()
+        .and_then(|h| h.timestamp.as_ref())
+        .map(|t| t.seconds)
+        .unwrap_or(0);
+
+    let pools: Vec<Pool> = blk
+        .receipts()
+        .flat_map(|receipt| {
+            let tx_hash = Hex(&receipt.transaction.hash).to_string();
+            receipt
+                .receipt
+                .logs
+                .iter()
+                .filter(|log| log.address == CL_FACTORY)
+                .filter_map(move |log| {
+                    let created = PoolCreated::match_and_decode(log)?;
+                    Some(Pool {
+                        address: Hex(&created.pool).to_string(),
+                        token0: Hex(&created.token0).to_string(),
+                        token1: Hex(&created.token1).to_string(),
+                        stable: false,
+                        block_number: blk.number,
+                        transaction_hash: tx_hash.clone(),
+                        timestamp: timestamp as u64,
+                        tick_spacing: created.tick_spacing.to_i32(),
+                        log_index: log.index as u64,
+                        ordinal: log.ordinal,
+                    })
+                })
+        })
+        .collect();
+
+    Ok(Pools { pools })
+}
+
+/// Extract Slipstream CLPool swap events
+#[substreams::handlers::map]
+pub fn map_cl_swaps(
+    blk: eth::Block,
+    pools: StoreGetProto<Pool>,
+    tokens: StoreGetProto<Token>,
+) -> Result<ClSwapEvents, substreams::errors::Error> {
+    let timestamp = blk
+        .header
+        .as_ref()
+        .and_then(|h| h.timestamp.as_ref())
+        .map(|t| t.seconds)
+        .unwrap_or(0);
+
+    let mut swaps = Vec::new();
+
+    for receipt in blk.receipts() {
+        let tx_hash = Hex(&receipt.transaction.hash).to_string();
+
+        for log in &receipt.receipt.logs {
+            let Some(swap) = Swap::match_and_decode(log) else {
+                continue;
+            };
+            let Some(pool) = aerodrome_pool(&pools, &log.address) else {
+                continue;
+            };
+            let decimals0 = token_decimals(&tokens, &pool.token0);
+            let decimals1 = token_decimals(&tokens, &pool.token1);
+
+            let direction = SwapDirection::from_deltas(&swap.amount0, &swap.amount1);
+            let (token_in, token_out, decimals_in, decimals_out) = if direction.zero_for_one {
+                (&pool.token0, &pool.token1, decimals0, decimals1)
+            } else {
+                (&pool.token1, &pool.token0, decimals1, decimals0)
+            };
+            let amount_in = direction.amount_in.to_string();
+            let amount_out = direction.amount_out.to_string();
+            let amount_in_decimal = to_decimal(&amount_in, decimals_in);
+            let amount_out_decimal = to_decimal(&amount_out, decimals_out);
+
+            let (amount0, amount1) = if direction.zero_for_one {
+                (&amount_in_decimal, &amount_out_decimal)
+            } else {
+                (&amount_out_decimal, &amount_in_decimal)
+            };
+            let price0 = prices::ratio(amount1, amount0);
+            let price1 = prices::ratio(amount0, amount1);
+            let pool_price0 =
+                prices::sqrt_price_x96_to_price0(&swap.sqrt_price_x96, decimals0, decimals1);
+
+            let amount0 = swap.amount0.to_string();
+            let amount1 = swap.amount1.to_string();
+
+            swaps.push(AerodromeClSwap {
+                block_number: blk.number,
+                transaction_hash: tx_hash.clone(),
+                log_index: log.index as u64,
+                pool_address: pool.address.clone(),
+                sender: Hex(&swap.sender).to_string(),
+                recipient: Hex(&swap.recipient).to_string(),
+                amount0_decimal: to_decimal(&amount0, decimals0).to_string(),
+                amount1_decimal: to_decimal(&amount1, decimals1).to_string(),
+                amount0,
+                amount1,
+                sqrt_price_x96: swap.sqrt_price_x96.to_string(),
+                liquidity: swap.liquidity.to_string(),
+                tick: swap.tick.to_i32(),
+                timestamp: timestamp as u64,
+                tick_spacing: pool.tick_spacing,
+                token_in: token_in.clone(),
+                token_out: token_out.clone(),
+                amount_in_decimal: amount_in_decimal.to_string(),
+                amount_out_decimal: amount_out_decimal.to_string(),
+                amount_in,
+                amount_out,
+                price0: decimal_string(price0),
+                price1: decimal_string(price1),
+                pool_price0: decimal_string(pool_price0),
+                ordinal: log.ordinal,
+                token0: pool.token0,
+                token1: pool.token1,
+            });
+        }
+    }
+
+    let swap_count = swaps.len() as u32;
+    Ok(ClSwapEvents { swaps, swap_count })
+}
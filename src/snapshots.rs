@@ -0,0 +1,190 @@
+//! Daily and hourly pool snapshots
+//!
+//! One snapshot per pool and bucket, like a subgraph's PoolDayData and
+//! PoolHourData. Per-token volumes, fees and swap counts come from the per-pool
+//! `daily:` / `hourly:` keys of `store_swap_volumes`, unique traders from
+//! the same keys of `store_trader_counts`. A bucket is touched by every
+//! Sync of its pool; swaps always sync, so Syncs alone drive it.
+//!
+//! Reserves and TVL close at their value when the bucket was last touched.
+//! Once a later bucket of the same pool opens, the block that opens it
+//! re-emits the previous one with `closed` set.
+
+use crate::calendar::{format_date, format_hour};
+use crate::candles::bucket_start;
+use crate::pb::aerodrome::{PoolSnapshot, PoolSnapshots, SyncEvents};
+use std::collections::{BTreeMap, BTreeSet};
+use substreams::scalar::{BigDecimal, BigInt};
+use substreams::store::{
+    StoreGet, StoreGetBigDecimal, StoreGetBigInt, StoreGetInt64, StoreNew, StoreSet,
+    StoreSetIfNotExists, StoreSetIfNotExistsBigInt, StoreSetInt64,
+};
+
+/// Snapshot intervals: key scope and length in seconds
+pub const INTERVALS: [(&str, u64); 2] = [("daily", 86_400), ("hourly", 3_600)];
+
+/// Label of the bucket starting at `start`, as used in store keys
+fn bucket_label(scope: &str, start: u64) -> String {
+    if scope == "daily" {
+        format_date(start)
+    } else {
+        format_hour(start)
+    }
+}
+
+/// Store key prefix of a pool's bucket, shared with `store_swap_volumes`
+fn snapshot_key(scope: &str, start: u64, pool: &str) -> String {
+    format!("{}:{}:pool:{}", scope, bucket_label(scope, start), pool)
+}
+
+/// Store handler for the reserves of each pool when a bucket opens
+///
+/// The first Sync of a bucket records the reserves it replaces, zero for a
+/// pool created within the bucket.
+///
+/// Key formats:
+/// - `daily:{date}:pool:{address}:reserve0` / `...:reserve1` - Opening reserves of the day
+/// - `hourly:{hour}:pool:{address}:reserve0` / `...:reserve1` - Opening reserves of the hour
+#[substreams::handlers::store]
+pub fn store_pool_snapshot_open(
+    syncs: SyncEvents,
+    reserves: StoreGetBigInt,
+    store: StoreSetIfNotExistsBigInt,
+) {
+    for sync in &syncs.events {
+        for (scope, interval) in INTERVALS {
+            let start = bucket_start(sync.timestamp, interval);
+            let key = snapshot_key(scope, start, &sync.pool_address);
+            for reserve in ["reserve0", "reserve1"] {
+                let before = reserves
+                    .get_at(
+                        sync.ordinal.saturating_sub(1),
+                        format!("pool:{}:{}", sync.pool_address, reserve),
+                    )
+                    .unwrap_or_else(BigInt::zero);
+                store.set_if_not_exists(sync.ordinal, format!("{}:{}", key, reserve), &before);
+            }
+        }
+    }
+}
+
+/// Store handler for the latest snapshot bucket of each pool and interval
+///
+/// Key formats:
+/// - `daily:{address}` / `hourly:{address}` - Start of the most recent bucket
+#[substreams::handlers::store]
+pub fn store_pool_snapshot_latest(syncs: SyncEvents, store: StoreSetInt64) {
+    for sync in &syncs.events {
+        for (scope, interval) in INTERVALS {
+            let start = bucket_start(sync.timestamp, interval);
+            store.set(
+                0,
+                format!("{}:{}", scope, sync.pool_address),
+                &(start as i64),
+            );
+        }
+    }
+}
+
+/// Emit the snapshot of every pool bucket touched in this block and every
+/// bucket it closed
+#[substreams::handlers::map]
+pub fn map_pool_snapshots(
+    syncs: SyncEvents,
+    volumes: StoreGetBigInt,
+    volumes_usd: StoreGetBigDecimal,
+    traders: StoreGetBigInt,
+    open: StoreGetBigInt,
+    latest: StoreGetInt64,
+    reserves: StoreGetBigInt,
+    pool_tvl: StoreGetBigDecimal,
+) -> Result<PoolSnapshots, substreams::errors::Error> {
+    // Buckets touched by this block, per interval and pool
+    let mut buckets: BTreeMap<(&str, u64, &str), BTreeSet<u64>> = BTreeMap::new();
+    for sync in &syncs.events {
+        for (scope, interval) in INTERVALS {
+            buckets
+                .entry((scope, interval, sync.pool_address.as_str()))
+                .or_default()
+                .insert(bucket_start(sync.timestamp, interval));
+        }
+    }
+
+    let mut snapshots = Vec::new();
+    for ((scope, interval, pool), mut touched) in buckets {
+        // The bucket open before this block, if it is not the one being updated
+        if let Some(previous) = latest.get_first(format!("{}:{}", scope, pool)) {
+            touched.insert(previous as u64);
+        }
+        let current = *touched.last().expect("at least one bucket per entry");
+
+        for start in touched {
+            let key = snapshot_key(scope, start, pool);
+            let closed = start != current;
+            let bigint = |store: &StoreGetBigInt, key: String| {
+                store.get_last(key).unwrap_or_else(BigInt::zero)
+            };
+            // A closed bucket ended before this block: its close is the state
+            // at the start of the block
+            let close = |reserve: &str| {
+                let key = format!("pool:{}:{}", pool, reserve);
+                let value = if closed {
+                    reserves.get_first(key)
+                } else {
+                    reserves.get_last(key)
+                };
+                value.unwrap_or_else(BigInt::zero).to_string()
+            };
+            let tvl_key = format!("pool:{}", pool);
+            let tvl = if closed {
+                pool_tvl.get_first(tvl_key)
+            } else {
+                pool_tvl.get_last(tvl_key)
+            };
+
+            snapshots.push(PoolSnapshot {
+                pool_address: pool.to_string(),
+                interval_seconds: interval,
+                period_start: start,
+                bucket: bucket_label(scope, start),
+                open_reserve0: bigint(&open, format!("{}:reserve0", key)).to_string(),
+                open_reserve1: bigint(&open, format!("{}:reserve1", key)).to_string(),
+                close_reserve0: close("reserve0"),
+                close_reserve1: close("reserve1"),
+                volume0: bigint(&volumes, format!("{}:volume0", key)).to_string(),
+                volume1: bigint(&volumes, format!("{}:volume1", key)).to_string(),
+                volume_usd: volumes_usd
+                    .get_last(format!("{}:volume_usd", key))
+                    .unwrap_or_else(BigDecimal::zero)
+                    .to_string(),
+                fees0: bigint(&volumes, format!("{}:fees0", key)).to_string(),
+                fees1: bigint(&volumes, format!("{}:fees1", key)).to_string(),
+                swap_count: bigint(&volumes, format!("{}:count", key)).to_u64(),
+                unique_traders: bigint(&traders, key.clone()).to_u64(),
+                tvl_usd: tvl.map(|tvl| tvl.to_string()).unwrap_or_default(),
+                closed,
+            });
+        }
+    }
+
+    Ok(PoolSnapshots { snapshots })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshot_key() {
+        // 2024-01-01 01:07:30 UTC
+        let timestamp = 1704071250;
+        assert_eq!(
+            snapshot_key("daily", bucket_start(timestamp, 86_400), "cdac0d6c"),
+            "daily:2024-01-01:pool:cdac0d6c"
+        );
+        assert_eq!(
+            snapshot_key("hourly", bucket_start(timestamp, 3_600), "cdac0d6c"),
+            "hourly:2024-01-01-01:pool:cdac0d6c"
+        );
+    }
+}
@@ -1,65 +1,216 @@
 //! Store modules for Aerodrome Substreams
 //!
 //! Provides persistent state tracking across blocks for:
-//! - Cumulative swap volumes by pool
-//! - Unique trader (wallet) tracking
+//! - Pools created by the Aerodrome PoolFactory and the Slipstream CLFactory
+//! - Token metadata (decimals, symbol, name)
+//! - Pool reserves, versioned by log ordinal
+//! - Swap counts, per-token and USD volumes, and LP fees by pool
+//! - Unique trader (wallet) tracking and counts
 //! - Pool statistics
-//! - Daily/hourly aggregations
+//! - Hourly, daily, weekly, monthly and per-epoch aggregations
 
-use crate::pb::aerodrome::SwapEvents;
-use substreams::scalar::BigInt;
-use substreams::store::{StoreAdd, StoreAddBigInt, StoreNew, StoreSetIfNotExists, StoreSetIfNotExistsString};
+use crate::calendar::{epoch_number, format_date, format_hour, format_iso_week, format_month};
+use crate::math::parse_bigint;
+use crate::pb::aerodrome::{Pool, Pools, SwapEvents, SyncEvents, Token, Tokens};
+use std::collections::HashSet;
+use substreams::pb::substreams::store_delta::Operation;
+use substreams::scalar::{BigDecimal, BigInt};
+use substreams::store::{
+    DeltaString, Deltas, StoreAdd, StoreAddBigDecimal, StoreAddBigInt, StoreNew, StoreSet,
+    StoreSetBigInt, StoreSetIfNotExists, StoreSetIfNotExistsInt64, StoreSetIfNotExistsProto,
+    StoreSetIfNotExistsString, StoreSetProto,
+};
 
-/// Store handler for tracking cumulative swap volumes by pool
+/// Store handler for pools created by the Aerodrome PoolFactory
 ///
 /// Key formats:
-/// - `pool:{address}:volume` - Total volume for pool
-/// - `pool:{address}:count` - Total swap count for pool
-/// - `daily:{date}:volume` - Daily volume
-/// - `daily:{date}:count` - Daily swap count
-/// - `hourly:{hour}:volume` - Hourly volume
-/// - `total:volume` - Protocol-wide total volume
+/// - `pool:{address}` - Pool with its tokens and stable flag
+#[substreams::handlers::store]
+pub fn store_pools(pools: Pools, store: StoreSetProto<Pool>) {
+    for pool in &pools.pools {
+        store.set(0, format!("pool:{}", pool.address), pool);
+    }
+}
+
+/// Store handler for pools created by the Slipstream CLFactory
+///
+/// Key formats:
+/// - `pool:{address}` - Pool with its tokens and tick spacing
+#[substreams::handlers::store]
+pub fn store_cl_pools(pools: Pools, store: StoreSetProto<Pool>) {
+    for pool in &pools.pools {
+        store.set(0, format!("pool:{}", pool.address), pool);
+    }
+}
+
+/// Store handler for every token paired in a pool (V2-style or Slipstream)
+///
+/// Lets `map_new_tokens` skip the eth_calls of tokens seen in earlier blocks.
+///
+/// Key formats:
+/// - `token:{address}` - Block of the first pool pairing the token
+#[substreams::handlers::store]
+pub fn store_pool_tokens(pools: Pools, cl_pools: Pools, store: StoreSetIfNotExistsInt64) {
+    for pool in pools.pools.iter().chain(&cl_pools.pools) {
+        for address in [&pool.token0, &pool.token1] {
+            store.set_if_not_exists(0, format!("token:{}", address), &(pool.block_number as i64));
+        }
+    }
+}
+
+/// Store handler for token metadata of every pool token
+///
+/// Key formats:
+/// - `token:{address}` - Token with decimals, symbol and name
+#[substreams::handlers::store]
+pub fn store_tokens(tokens: Tokens, store: StoreSetIfNotExistsProto<Token>) {
+    for token in &tokens.tokens {
+        store.set_if_not_exists(0, format!("token:{}", token.address), token);
+    }
+}
+
+/// Store handler for pool reserves from Sync events
+///
+/// Values are written at the Sync log ordinal so readers can query the
+/// reserves in effect at any point of the block with `get_at`.
+///
+/// Key formats:
+/// - `pool:{address}:reserve0` - Reserve of token0 (raw units)
+/// - `pool:{address}:reserve1` - Reserve of token1 (raw units)
+#[substreams::handlers::store]
+pub fn store_pool_reserves(syncs: SyncEvents, store: StoreSetBigInt) {
+    for sync in &syncs.events {
+        store.set(
+            sync.ordinal,
+            format!("pool:{}:reserve0", sync.pool_address),
+            &parse_bigint(&sync.reserve0),
+        );
+        store.set(
+            sync.ordinal,
+            format!("pool:{}:reserve1", sync.pool_address),
+            &parse_bigint(&sync.reserve1),
+        );
+    }
+}
+
+/// Store handler for tracking cumulative swap counts, volumes and LP fees
+///
+/// Volumes are kept per pool token in raw units: `volume0` is token0 traded
+/// in or out and `volume1` token1, so amounts of different tokens are never
+/// added together. Scopes spanning several pools only count swaps; their
+/// volume is in USD, in `store_swap_volumes_usd`.
+///
+/// Key formats:
+/// - `pool:{address}:{metric}` - Pool totals
+/// - `daily:{date}:pool:{address}:{metric}` - Per-pool daily stats
+/// - `hourly:{hour}:pool:{address}:{metric}` - Per-pool hourly stats
+/// - `epoch:{n}:pool:{address}:{metric}` - Per-pool epoch stats
+///
+///   where `metric` is `count`, `volume0` / `volume1` or LP fees `fees0` / `fees1`
+/// - `daily:{date}:count` / `hourly:{hour}:count` - Daily and hourly swap count
+/// - `weekly:{iso_week}:count` / `monthly:{month}:count` - ISO week and calendar month swap count
+/// - `epoch:{n}:count` - Aerodrome epoch swap count
+/// - `trader:{address}:count` - Total swap count for wallet
 /// - `total:swaps` - Protocol-wide swap count
 #[substreams::handlers::store]
 pub fn store_swap_volumes(swaps: SwapEvents, store: StoreAddBigInt) {
     for swap in &swaps.swaps {
-        // Calculate total amount
-        let amount_in: u64 = swap.amount0_in.parse().unwrap_or(0)
-            + swap.amount1_in.parse().unwrap_or(0);
+        let volume0 = parse_bigint(&swap.amount0_in) + parse_bigint(&swap.amount0_out);
+        let volume1 = parse_bigint(&swap.amount1_in) + parse_bigint(&swap.amount1_out);
 
-        if amount_in == 0 {
+        if volume0.is_zero() && volume1.is_zero() {
             continue;
         }
 
-        // Store volume by pool
-        let pool_volume_key = format!("pool:{}:volume", swap.pool_address);
-        store.add(0, &pool_volume_key, &BigInt::from(amount_in));
+        let date = format_date(swap.timestamp);
+        let hour = format_hour(swap.timestamp);
+        let epoch = epoch_number(swap.timestamp);
+        let one = BigInt::from(1u64);
 
-        // Store swap count by pool
-        let pool_count_key = format!("pool:{}:count", swap.pool_address);
-        store.add(0, &pool_count_key, &BigInt::from(1u64));
+        // Store swap counts, protocol-wide and by calendar bucket
+        store.add(0, "total:swaps", &one);
+        let buckets = [
+            format!("daily:{}", date),
+            format!("hourly:{}", hour),
+            format!("weekly:{}", format_iso_week(swap.timestamp)),
+            format!("monthly:{}", format_month(swap.timestamp)),
+            format!("epoch:{}", epoch),
+        ];
+        for bucket in buckets {
+            store.add(0, format!("{}:count", bucket), &one);
+        }
 
-        // Store total protocol volume
-        store.add(0, "total:volume", &BigInt::from(amount_in));
+        // Store swap count by trader
+        if !swap.sender.is_empty() {
+            store.add(0, format!("trader:{}:count", swap.sender), &one);
+        }
 
-        // Store total swap count
-        store.add(0, "total:swaps", &BigInt::from(1u64));
+        // Store counts, per-token volumes and LP fees by pool, all-time and by bucket
+        let pool = &swap.pool_address;
+        let scopes = [
+            format!("pool:{}", pool),
+            format!("daily:{}:pool:{}", date, pool),
+            format!("hourly:{}:pool:{}", hour, pool),
+            format!("epoch:{}:pool:{}", epoch, pool),
+        ];
+        let metrics = [
+            ("count", one),
+            ("volume0", volume0),
+            ("volume1", volume1),
+            ("fees0", parse_bigint(&swap.fee0)),
+            ("fees1", parse_bigint(&swap.fee1)),
+        ];
+        for scope in &scopes {
+            for (metric, value) in &metrics {
+                if !value.is_zero() {
+                    store.add(0, format!("{}:{}", scope, metric), value);
+                }
+            }
+        }
+    }
+}
 
-        // Store daily volume
+/// Store handler for tracking swap volumes in USD
+///
+/// Swaps without a USD value (neither token priced) are skipped. Keys mirror
+/// the scopes of `store_swap_volumes` with a `volume_usd` metric:
+/// - `pool:{address}:volume_usd` - Total USD volume for pool
+/// - `daily:{date}:volume_usd` / `hourly:{hour}:volume_usd` - Daily and hourly USD volume
+/// - `weekly:{iso_week}:volume_usd` / `monthly:{month}:volume_usd` - ISO week and month USD volume
+/// - `epoch:{n}:volume_usd` - Aerodrome epoch USD volume
+/// - `daily:{date}:pool:{address}:volume_usd` - Per-pool daily USD volume
+/// - `hourly:{hour}:pool:{address}:volume_usd` - Per-pool hourly USD volume
+/// - `epoch:{n}:pool:{address}:volume_usd` - Per-pool epoch USD volume
+/// - `trader:{address}:volume_usd` - Total USD volume for wallet
+/// - `total:volume_usd` - Protocol-wide USD volume
+#[substreams::handlers::store]
+pub fn store_swap_volumes_usd(swaps: SwapEvents, store: StoreAddBigDecimal) {
+    for swap in &swaps.swaps {
+        let Ok(amount_usd) = swap.amount_usd.parse::<BigDecimal>() else {
+            continue;
+        };
         let date = format_date(swap.timestamp);
-        let daily_volume_key = format!("daily:{}:volume", date);
-        store.add(0, &daily_volume_key, &BigInt::from(amount_in));
-
-        let daily_count_key = format!("daily:{}:count", date);
-        store.add(0, &daily_count_key, &BigInt::from(1u64));
-
-        // Store hourly volume
         let hour = format_hour(swap.timestamp);
-        let hourly_volume_key = format!("hourly:{}:volume", hour);
-        store.add(0, &hourly_volume_key, &BigInt::from(amount_in));
+        let epoch = epoch_number(swap.timestamp);
 
-        let hourly_count_key = format!("hourly:{}:count", hour);
-        store.add(0, &hourly_count_key, &BigInt::from(1u64));
+        let mut keys = vec![
+            format!("pool:{}:volume_usd", swap.pool_address),
+            "total:volume_usd".to_string(),
+            format!("daily:{}:volume_usd", date),
+            format!("hourly:{}:volume_usd", hour),
+            format!("weekly:{}:volume_usd", format_iso_week(swap.timestamp)),
+            format!("monthly:{}:volume_usd", format_month(swap.timestamp)),
+            format!("epoch:{}:volume_usd", epoch),
+            format!("daily:{}:pool:{}:volume_usd", date, swap.pool_address),
+            format!("hourly:{}:pool:{}:volume_usd", hour, swap.pool_address),
+            format!("epoch:{}:pool:{}:volume_usd", epoch, swap.pool_address),
+        ];
+        if !swap.sender.is_empty() {
+            keys.push(format!("trader:{}:volume_usd", swap.sender));
+        }
+        for key in keys {
+            store.add(0, key, &amount_usd);
+        }
     }
 }
 
@@ -69,6 +220,11 @@ pub fn store_swap_volumes(swaps: SwapEvents, store: StoreAddBigInt) {
 /// - `trader:{address}` - First seen timestamp for wallet
 /// - `daily:{date}:trader:{address}` - Daily unique trader tracking
 /// - `pool:{pool}:trader:{address}` - Per-pool unique trader tracking
+/// - `weekly:{iso_week}:trader:{address}` / `monthly:{month}:trader:{address}` - ISO week and
+///   calendar month unique trader tracking
+/// - `epoch:{n}:trader:{address}` - Epoch unique trader tracking
+/// - `daily:{date}:pool:{pool}:trader:{address}` / `hourly:{hour}:pool:{pool}:trader:{address}` -
+///   Per-pool unique traders of each day and hour
 #[substreams::handlers::store]
 pub fn store_unique_traders(swaps: SwapEvents, store: StoreSetIfNotExistsString) {
     for swap in &swaps.swaps {
@@ -86,12 +242,94 @@ pub fn store_unique_traders(swaps: SwapEvents, store: StoreSetIfNotExistsString)
         let daily_trader_key = format!("daily:{}:trader:{}", date, swap.sender);
         store.set_if_not_exists(0, &daily_trader_key, &swap.block_number.to_string());
 
+        // Track weekly and monthly unique traders
+        let week = format_iso_week(swap.timestamp);
+        let weekly_trader_key = format!("weekly:{}:trader:{}", week, swap.sender);
+        store.set_if_not_exists(0, &weekly_trader_key, &swap.block_number.to_string());
+
+        let month = format_month(swap.timestamp);
+        let monthly_trader_key = format!("monthly:{}:trader:{}", month, swap.sender);
+        store.set_if_not_exists(0, &monthly_trader_key, &swap.block_number.to_string());
+
+        // Track epoch unique traders
+        let epoch = epoch_number(swap.timestamp);
+        let epoch_trader_key = format!("epoch:{}:trader:{}", epoch, swap.sender);
+        store.set_if_not_exists(0, &epoch_trader_key, &swap.block_number.to_string());
+
         // Track traders per pool
         let pool_trader_key = format!("pool:{}:trader:{}", swap.pool_address, swap.sender);
         store.set_if_not_exists(0, &pool_trader_key, &swap.block_number.to_string());
+
+        // Track traders per pool and snapshot bucket
+        let hour = format_hour(swap.timestamp);
+        for bucket in [format!("daily:{}", date), format!("hourly:{}", hour)] {
+            let key = format!(
+                "{}:pool:{}:trader:{}",
+                bucket, swap.pool_address, swap.sender
+            );
+            store.set_if_not_exists(0, &key, &swap.block_number.to_string());
+        }
     }
 }
 
+/// Store handler for counting unique traders
+///
+/// Consumes the `store_unique_traders` deltas: a created key is a trader seen
+/// for the first time in its scope, so each one counts once. A trader active
+/// on a day is new if its first swap ever is in this block, since that swap
+/// also opened its day.
+///
+/// Key formats:
+/// - `total` - Traders ever seen
+/// - `daily:{date}` / `weekly:{iso_week}` / `monthly:{month}` - Active traders (DAU/WAU/MAU)
+/// - `daily:{date}:new` / `daily:{date}:returning` - Active traders by first-seen day
+/// - `epoch:{n}` - Active traders per Aerodrome epoch
+/// - `pool:{address}` - Traders of the pool
+/// - `daily:{date}:pool:{address}` / `hourly:{hour}:pool:{address}` - Pool traders per bucket
+#[substreams::handlers::store]
+pub fn store_trader_counts(traders: Deltas<DeltaString>, store: StoreAddBigInt) {
+    let created: Vec<_> = traders
+        .deltas
+        .iter()
+        .filter(|delta| delta.operation == Operation::Create)
+        .collect();
+    let new_traders: HashSet<&str> = created
+        .iter()
+        .filter_map(|delta| delta.key.strip_prefix("trader:"))
+        .collect();
+
+    for delta in created {
+        let Some((scope, trader)) = trader_count_key(&delta.key) else {
+            continue;
+        };
+        store.add(delta.ordinal, &scope, BigInt::one());
+
+        // Protocol-wide day only, not a pool's
+        if let Some(date) = scope.strip_prefix("daily:").filter(|d| !d.contains(':')) {
+            let kind = if new_traders.contains(trader) {
+                "new"
+            } else {
+                "returning"
+            };
+            store.add(
+                delta.ordinal,
+                format!("daily:{}:{}", date, kind),
+                BigInt::one(),
+            );
+        }
+    }
+}
+
+/// Counter key and trader of a `store_unique_traders` key, the scope before
+/// `:trader:{address}` or `total` for a first-seen marker
+fn trader_count_key(key: &str) -> Option<(String, &str)> {
+    if let Some(trader) = key.strip_prefix("trader:") {
+        return Some(("total".to_string(), trader));
+    }
+    let (scope, trader) = key.rsplit_once(":trader:")?;
+    Some((scope.to_string(), trader))
+}
+
 /// Store handler for tracking pool statistics
 ///
 /// Key formats:
@@ -100,10 +338,7 @@ pub fn store_unique_traders(swaps: SwapEvents, store: StoreSetIfNotExistsString)
 #[substreams::handlers::store]
 pub fn store_pool_stats(swaps: SwapEvents, store: StoreAddBigInt) {
     for swap in &swaps.swaps {
-        let amount_in: u64 = swap.amount0_in.parse().unwrap_or(0)
-            + swap.amount1_in.parse().unwrap_or(0);
-
-        if amount_in == 0 {
+        if parse_bigint(&swap.amount0_in).is_zero() && parse_bigint(&swap.amount1_in).is_zero() {
             continue;
         }
 
@@ -113,76 +348,24 @@ pub fn store_pool_stats(swaps: SwapEvents, store: StoreAddBigInt) {
     }
 }
 
-/// Format Unix timestamp to YYYY-MM-DD date string
-fn format_date(timestamp: u64) -> String {
-    let days = timestamp / 86400;
-    let mut year = 1970u64;
-    let mut remaining_days = days;
-
-    loop {
-        let days_in_year = if is_leap_year(year) { 366 } else { 365 };
-        if remaining_days < days_in_year {
-            break;
-        }
-        remaining_days -= days_in_year;
-        year += 1;
-    }
-
-    let days_in_months: [u64; 12] = if is_leap_year(year) {
-        [31, 29, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31]
-    } else {
-        [31, 28, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31]
-    };
-
-    let mut month = 1u64;
-    for days_in_month in days_in_months.iter() {
-        if remaining_days < *days_in_month {
-            break;
-        }
-        remaining_days -= days_in_month;
-        month += 1;
-    }
-
-    let day = remaining_days + 1;
-    format!("{:04}-{:02}-{:02}", year, month, day)
-}
-
-/// Format Unix timestamp to YYYY-MM-DD-HH hour string
-fn format_hour(timestamp: u64) -> String {
-    let date = format_date(timestamp);
-    let hour = (timestamp % 86400) / 3600;
-    format!("{}-{:02}", date, hour)
-}
-
-/// Check if a year is a leap year
-#[inline]
-fn is_leap_year(year: u64) -> bool {
-    (year % 4 == 0 && year % 100 != 0) || (year % 400 == 0)
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
-    fn test_format_date() {
-        assert_eq!(format_date(0), "1970-01-01");
-        assert_eq!(format_date(1705276800), "2024-01-15");
-        assert_eq!(format_date(1582934400), "2020-02-29"); // Leap year
-    }
-
-    #[test]
-    fn test_format_hour() {
-        assert_eq!(format_hour(0), "1970-01-01-00");
-        assert_eq!(format_hour(3600), "1970-01-01-01");
-        assert_eq!(format_hour(86399), "1970-01-01-23");
-    }
-
-    #[test]
-    fn test_is_leap_year() {
-        assert!(is_leap_year(2020));
-        assert!(is_leap_year(2000));
-        assert!(!is_leap_year(2100));
-        assert!(!is_leap_year(2023));
+    fn test_trader_count_key() {
+        assert_eq!(
+            trader_count_key("trader:cf77a3ba"),
+            Some(("total".to_string(), "cf77a3ba"))
+        );
+        assert_eq!(
+            trader_count_key("daily:2024-01-15:trader:cf77a3ba"),
+            Some(("daily:2024-01-15".to_string(), "cf77a3ba"))
+        );
+        assert_eq!(
+            trader_count_key("hourly:2024-01-15-13:pool:cdac0d6c:trader:cf77a3ba"),
+            Some(("hourly:2024-01-15-13:pool:cdac0d6c".to_string(), "cf77a3ba"))
+        );
+        assert_eq!(trader_count_key("pool:cdac0d6c:volume"), None);
     }
 }
@@ -0,0 +1,114 @@
+//! Directional view of Aerodrome swaps
+//!
+//! A pool `Swap` reports four amounts indexed by reserve side. Traders care
+//! about what went in and what came out, so the net flow of each side is
+//! used to derive a single `token_in -> token_out` trade. Slipstream pools
+//! already report signed net deltas.
+
+use substreams::scalar::BigInt;
+
+/// Net trade derived from the `amount{0,1}_{in,out}` quadruple
+#[derive(Debug, Clone, PartialEq)]
+pub struct SwapDirection {
+    /// `true` when token0 is sold for token1
+    pub zero_for_one: bool,
+    pub amount_in: BigInt,
+    pub amount_out: BigInt,
+    /// Both tokens were sent in (or both taken out), as in flash swaps
+    pub two_sided: bool,
+}
+
+impl SwapDirection {
+    pub fn new(
+        amount0_in: &BigInt,
+        amount1_in: &BigInt,
+        amount0_out: &BigInt,
+        amount1_out: &BigInt,
+    ) -> Self {
+        let zero = BigInt::zero();
+        let two_sided = (amount0_in > &zero && amount1_in > &zero)
+            || (amount0_out > &zero && amount1_out > &zero);
+
+        let net0 = amount0_in.clone() - amount0_out.clone();
+        let net1 = amount1_in.clone() - amount1_out.clone();
+
+        SwapDirection {
+            two_sided,
+            ..Self::from_deltas(&net0, &net1)
+        }
+    }
+
+    /// Trade from signed pool balance deltas, positive when paid into the pool
+    ///
+    /// This is how Slipstream (concentrated liquidity) pools report swaps.
+    pub fn from_deltas(amount0: &BigInt, amount1: &BigInt) -> Self {
+        let zero = BigInt::zero();
+
+        if amount0 > &zero || (amount0.is_zero() && amount1 < &zero) {
+            SwapDirection {
+                zero_for_one: true,
+                amount_in: amount0.clone(),
+                amount_out: amount1.neg(),
+                two_sided: false,
+            }
+        } else {
+            SwapDirection {
+                zero_for_one: false,
+                amount_in: amount1.clone(),
+                amount_out: amount0.neg(),
+                two_sided: false,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn direction(a0_in: u64, a1_in: u64, a0_out: u64, a1_out: u64) -> SwapDirection {
+        SwapDirection::new(
+            &BigInt::from(a0_in),
+            &BigInt::from(a1_in),
+            &BigInt::from(a0_out),
+            &BigInt::from(a1_out),
+        )
+    }
+
+    #[test]
+    fn test_zero_for_one() {
+        let d = direction(1_000, 0, 0, 2_990);
+        assert!(d.zero_for_one);
+        assert_eq!(d.amount_in, BigInt::from(1_000u64));
+        assert_eq!(d.amount_out, BigInt::from(2_990u64));
+        assert!(!d.two_sided);
+    }
+
+    #[test]
+    fn test_one_for_zero() {
+        let d = direction(0, 3_000, 997, 0);
+        assert!(!d.zero_for_one);
+        assert_eq!(d.amount_in, BigInt::from(3_000u64));
+        assert_eq!(d.amount_out, BigInt::from(997u64));
+        assert!(!d.two_sided);
+    }
+
+    #[test]
+    fn test_two_sided_uses_net_flows() {
+        // Flash swap returning part of token1 alongside token0
+        let d = direction(1_000, 500, 0, 3_490);
+        assert!(d.zero_for_one);
+        assert_eq!(d.amount_in, BigInt::from(1_000u64));
+        assert_eq!(d.amount_out, BigInt::from(2_990u64));
+        assert!(d.two_sided);
+    }
+
+    #[test]
+    fn test_from_signed_deltas() {
+        // Slipstream: token1 paid in, token0 paid out
+        let d = SwapDirection::from_deltas(&BigInt::from(-997i64), &BigInt::from(3_000i64));
+        assert!(!d.zero_for_one);
+        assert_eq!(d.amount_in, BigInt::from(3_000u64));
+        assert_eq!(d.amount_out, BigInt::from(997u64));
+    }
+}
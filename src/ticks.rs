@@ -0,0 +1,250 @@
+//! Slipstream liquidity depth
+//!
+//! Every CL `Mint` adds `liquidityNet` at its lower tick and removes it at
+//! its upper tick (a `Burn` does the reverse), which gives the liquidity
+//! distribution across ticks in `store_cl_ticks`.
+//!
+//! Active liquidity is the pool liquidity reported by the latest `Swap`,
+//! adjusted by in-range mints and burns since then. Stores cannot read
+//! themselves, so the adjustment is tracked as a running total plus a
+//! checkpoint of that total taken at every swap:
+//! - `store_cl_pool_prices` - sqrtPrice, tick and liquidity from swaps (set)
+//! - `store_cl_in_range_liquidity` - in-range mint/burn liquidity (add)
+//! - `store_cl_liquidity_checkpoints` - in-range total at the latest swap (set)
+//!
+//! Pools that have not swapped yet have no known tick, so their active
+//! liquidity stays zero until the first swap reports it.
+
+use crate::math::parse_bigint;
+use crate::pb::aerodrome::{ClLiquidityDepth, ClPoolDepth, ClSwapEvents, ClTick, LiquidityEvents};
+use std::collections::BTreeMap;
+use substreams::pb::substreams::Clock;
+use substreams::scalar::BigInt;
+use substreams::store::{
+    DeltaBigInt, Deltas, StoreAdd, StoreAddBigInt, StoreGet, StoreGetBigInt, StoreNew, StoreSet,
+    StoreSetBigInt,
+};
+
+/// Store handler for liquidityNet per initialized tick
+///
+/// Key formats:
+/// - `pool:{address}:tick:{tick}` - Net liquidity added when crossing the tick upwards
+#[substreams::handlers::store]
+pub fn store_cl_ticks(events: LiquidityEvents, store: StoreAddBigInt) {
+    for event in &events.events {
+        let Some(liquidity) = signed_liquidity(&event.action, &event.liquidity) else {
+            continue;
+        };
+        if !event.concentrated {
+            continue;
+        }
+
+        store.add(
+            event.ordinal,
+            tick_key(&event.pool_address, event.tick_lower),
+            &liquidity,
+        );
+        store.add(
+            event.ordinal,
+            tick_key(&event.pool_address, event.tick_upper),
+            liquidity.neg(),
+        );
+    }
+}
+
+/// Store handler for the pool state reported by Slipstream swaps
+///
+/// Key formats:
+/// - `pool:{address}:sqrt_price` - sqrtPriceX96 after the swap
+/// - `pool:{address}:tick` - Current tick after the swap
+/// - `pool:{address}:liquidity` - Active liquidity after the swap
+#[substreams::handlers::store]
+pub fn store_cl_pool_prices(swaps: ClSwapEvents, store: StoreSetBigInt) {
+    for swap in &swaps.swaps {
+        let key = format!("pool:{}", swap.pool_address);
+        store.set(
+            swap.ordinal,
+            format!("{}:sqrt_price", key),
+            &parse_bigint(&swap.sqrt_price_x96),
+        );
+        store.set(
+            swap.ordinal,
+            format!("{}:tick", key),
+            &BigInt::from(swap.tick as i64),
+        );
+        store.set(
+            swap.ordinal,
+            format!("{}:liquidity", key),
+            &parse_bigint(&swap.liquidity),
+        );
+    }
+}
+
+/// Store handler for liquidity minted or burned around the current tick
+///
+/// Key formats:
+/// - `pool:{address}` - Cumulative in-range liquidity change
+#[substreams::handlers::store]
+pub fn store_cl_in_range_liquidity(
+    events: LiquidityEvents,
+    prices: StoreGetBigInt,
+    store: StoreAddBigInt,
+) {
+    for event in &events.events {
+        let Some(liquidity) = signed_liquidity(&event.action, &event.liquidity) else {
+            continue;
+        };
+        if !event.concentrated {
+            continue;
+        }
+        let key = format!("pool:{}", event.pool_address);
+        let Some(tick) = prices.get_at(event.ordinal, format!("{}:tick", key)) else {
+            continue;
+        };
+
+        if in_range(tick.to_i32(), event.tick_lower, event.tick_upper) {
+            store.add(event.ordinal, key, liquidity);
+        }
+    }
+}
+
+/// Store handler checkpointing the in-range total at every swap
+///
+/// Key formats:
+/// - `pool:{address}` - `store_cl_in_range_liquidity` value at the latest swap
+#[substreams::handlers::store]
+pub fn store_cl_liquidity_checkpoints(
+    swaps: ClSwapEvents,
+    in_range: StoreGetBigInt,
+    store: StoreSetBigInt,
+) {
+    for swap in &swaps.swaps {
+        let key = format!("pool:{}", swap.pool_address);
+        let total = in_range
+            .get_at(swap.ordinal, &key)
+            .unwrap_or_else(BigInt::zero);
+        store.set(swap.ordinal, key, &total);
+    }
+}
+
+/// Emit changed ticks and the current state of every Slipstream pool
+/// touched by a swap, mint or burn in the block
+#[substreams::handlers::map]
+pub fn map_cl_liquidity_depth(
+    clock: Clock,
+    swaps: ClSwapEvents,
+    ticks: Deltas<DeltaBigInt>,
+    prices: StoreGetBigInt,
+    in_range: StoreGetBigInt,
+    checkpoints: StoreGetBigInt,
+) -> Result<ClLiquidityDepth, substreams::errors::Error> {
+    // Latest liquidityNet of every changed tick, grouped by pool
+    let mut changed: BTreeMap<&str, BTreeMap<i32, &BigInt>> = BTreeMap::new();
+    for delta in &ticks.deltas {
+        if let Some((pool, tick)) = parse_tick_key(&delta.key) {
+            changed
+                .entry(pool)
+                .or_default()
+                .insert(tick, &delta.new_value);
+        }
+    }
+    for swap in &swaps.swaps {
+        changed.entry(swap.pool_address.as_str()).or_default();
+    }
+
+    let pools = changed
+        .into_iter()
+        .map(|(pool, ticks)| {
+            let key = format!("pool:{}", pool);
+            let get = |store: &StoreGetBigInt, key: String| {
+                store.get_last(key).unwrap_or_else(BigInt::zero)
+            };
+
+            let liquidity = active_liquidity(
+                get(&prices, format!("{}:liquidity", key)),
+                get(&in_range, key.clone()),
+                get(&checkpoints, key.clone()),
+            );
+
+            ClPoolDepth {
+                pool_address: pool.to_string(),
+                liquidity: liquidity.to_string(),
+                sqrt_price_x96: get(&prices, format!("{}:sqrt_price", key)).to_string(),
+                tick: get(&prices, format!("{}:tick", key)).to_i32(),
+                ticks: ticks
+                    .into_iter()
+                    .map(|(tick, liquidity_net)| ClTick {
+                        tick,
+                        liquidity_net: liquidity_net.to_string(),
+                    })
+                    .collect(),
+            }
+        })
+        .collect();
+
+    Ok(ClLiquidityDepth {
+        block_number: clock.number,
+        timestamp: clock
+            .timestamp
+            .map(|t| t.seconds as u64)
+            .unwrap_or_default(),
+        pools,
+    })
+}
+
+/// Liquidity added (positive) or removed (negative) by a Mint or Burn
+fn signed_liquidity(action: &str, liquidity: &str) -> Option<BigInt> {
+    match action {
+        "mint" => Some(parse_bigint(liquidity)),
+        "burn" => Some(parse_bigint(liquidity).neg()),
+        _ => None,
+    }
+}
+
+/// A position is active when `tick_lower <= tick < tick_upper`
+fn in_range(tick: i32, tick_lower: i32, tick_upper: i32) -> bool {
+    tick_lower <= tick && tick < tick_upper
+}
+
+/// Liquidity at the latest swap plus in-range changes since then
+fn active_liquidity(at_swap: BigInt, in_range: BigInt, checkpoint: BigInt) -> BigInt {
+    at_swap + in_range - checkpoint
+}
+
+fn tick_key(pool: &str, tick: i32) -> String {
+    format!("pool:{}:tick:{}", pool, tick)
+}
+
+/// Split a `pool:{address}:tick:{tick}` key into its pool and tick
+fn parse_tick_key(key: &str) -> Option<(&str, i32)> {
+    let mut parts = key.split(':');
+    match (parts.next(), parts.next(), parts.next(), parts.next()) {
+        (Some("pool"), Some(pool), Some("tick"), Some(tick)) => Some((pool, tick.parse().ok()?)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_tick_key() {
+        let key = tick_key("abc", -887_220);
+        assert_eq!(parse_tick_key(&key), Some(("abc", -887_220)));
+        assert_eq!(parse_tick_key("pool:abc:sqrt_price"), None);
+    }
+
+    #[test]
+    fn test_active_liquidity_after_in_range_mint() {
+        // 500 minted in range before the swap, 200 after it
+        let liquidity = active_liquidity(
+            BigInt::from(10_000u64),
+            BigInt::from(700u64),
+            BigInt::from(500u64),
+        );
+        assert_eq!(liquidity, BigInt::from(10_200u64));
+        assert!(in_range(0, -60, 60));
+        assert!(!in_range(60, -60, 60));
+    }
+}
@@ -0,0 +1,122 @@
+//! ERC-20 token metadata resolution
+//!
+//! Resolves `decimals()`, `symbol()` and `name()` through `eth_call` the
+//! first time a token is paired in a pool. Tokens returning `bytes32` for `symbol()`/`name()` are
+//! decoded as right-padded UTF-8, and tokens whose calls revert fall back to
+//! empty strings and zero decimals, which leaves their amounts unscaled.
+
+use crate::abi::erc20::functions::{Decimals, Name, Symbol};
+use crate::pb::aerodrome::{Pools, Token, Tokens};
+use std::collections::HashSet;
+use substreams::store::{StoreGet, StoreGetInt64};
+use substreams::Hex;
+use substreams_ethereum::pb::eth::rpc::RpcResponse;
+use substreams_ethereum::rpc::RpcBatch;
+
+/// Resolve the metadata of pool tokens not seen before this block
+///
+/// `known` is `store_pool_tokens`, read as of the start of the block so the
+/// tokens it records for this block's pools still count as new.
+#[substreams::handlers::map]
+pub fn map_new_tokens(
+    pools: Pools,
+    cl_pools: Pools,
+    known: StoreGetInt64,
+) -> Result<Tokens, substreams::errors::Error> {
+    let mut seen = HashSet::new();
+    let mut tokens = Vec::new();
+
+    for pool in pools.pools.iter().chain(&cl_pools.pools) {
+        for address in [&pool.token0, &pool.token1] {
+            if !seen.insert(address.as_str()) {
+                continue;
+            }
+            if known.get_first(format!("token:{}", address)).is_some() {
+                continue;
+            }
+            let Ok(bytes) = Hex::decode(address) else {
+                continue;
+            };
+            tokens.push(fetch_token(&bytes));
+        }
+    }
+
+    Ok(Tokens { tokens })
+}
+
+/// Fetch token metadata for `address` with a single batched eth_call
+pub fn fetch_token(address: &[u8]) -> Token {
+    let responses = RpcBatch::new()
+        .add(Decimals {}, address.to_vec())
+        .add(Symbol {}, address.to_vec())
+        .add(Name {}, address.to_vec())
+        .execute()
+        .map(|r| r.responses)
+        .unwrap_or_default();
+
+    let decimals = responses
+        .first()
+        .and_then(RpcBatch::decode::<_, Decimals>)
+        .and_then(|d| u64::try_from(d).ok())
+        .unwrap_or(0);
+
+    Token {
+        address: Hex(address).to_string(),
+        name: responses.get(2).and_then(decode_string).unwrap_or_default(),
+        symbol: responses.get(1).and_then(decode_string).unwrap_or_default(),
+        decimals,
+    }
+}
+
+/// Decode a `string` or `bytes32` return value, `None` if the call reverted
+fn decode_string(response: &RpcResponse) -> Option<String> {
+    if response.failed {
+        return None;
+    }
+    decode_string_output(&response.raw)
+}
+
+fn decode_string_output(raw: &[u8]) -> Option<String> {
+    if let Ok(value) = Symbol::output(raw) {
+        return Some(value);
+    }
+
+    // Tokens predating the ERC-20 string convention return a bytes32
+    if raw.len() == 32 {
+        let end = raw.iter().position(|b| *b == 0).unwrap_or(raw.len());
+        return Some(String::from_utf8_lossy(&raw[..end]).into_owned());
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_string_output() {
+        // ABI-encoded string "AERO"
+        let mut raw = vec![0u8; 96];
+        raw[31] = 0x20;
+        raw[63] = 4;
+        raw[64..68].copy_from_slice(b"AERO");
+        assert_eq!(decode_string_output(&raw), Some("AERO".to_string()));
+    }
+
+    #[test]
+    fn test_decode_bytes32_output() {
+        let mut raw = vec![0u8; 32];
+        raw[..3].copy_from_slice(b"MKR");
+        assert_eq!(decode_string_output(&raw), Some("MKR".to_string()));
+    }
+
+    #[test]
+    fn test_decode_reverted_call() {
+        let response = RpcResponse {
+            raw: vec![],
+            failed: true,
+        };
+        assert_eq!(decode_string(&response), None);
+    }
+}
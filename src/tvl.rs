@@ -0,0 +1,163 @@
+//! Total value locked in V2-style pools
+//!
+//! A pool's TVL is the USD value of its reserves at every Sync. When only
+//! one token is priced the pool is valued at twice that side. Stores cannot
+//! read themselves, so protocol TVL is the running sum of the changes of
+//! every pool's TVL:
+//! - `store_pool_tvl` - pool TVL at its latest Sync (set)
+//! - `store_protocol_tvl` - sum of all pool TVLs (add, from the deltas above)
+//!
+//! Pools are revalued on their own Syncs only, so price moves reach a pool's
+//! TVL on its next reserve change.
+
+use crate::math::to_decimal;
+use crate::pb::aerodrome::{Pool, PoolTvl, PoolTvlSnapshots, SyncEvents, Token};
+use crate::{prices, pricing, token_decimals};
+use std::collections::BTreeSet;
+use substreams::pb::substreams::Clock;
+use substreams::scalar::{BigDecimal, BigInt};
+use substreams::store::{
+    DeltaBigDecimal, Deltas, StoreAdd, StoreAddBigDecimal, StoreGet, StoreGetBigDecimal,
+    StoreGetBigInt, StoreGetProto, StoreNew, StoreSet, StoreSetBigDecimal,
+};
+
+/// Store handler for pool TVL in USD
+///
+/// Pools are only written once at least one of their tokens is priced.
+///
+/// Key formats:
+/// - `pool:{address}` - TVL at the pool's latest Sync
+#[substreams::handlers::store]
+pub fn store_pool_tvl(
+    syncs: SyncEvents,
+    pools: StoreGetProto<Pool>,
+    tokens: StoreGetProto<Token>,
+    token_prices: StoreGetBigDecimal,
+    store: StoreSetBigDecimal,
+) {
+    for sync in &syncs.events {
+        let Some(pool) = pools.get_last(format!("pool:{}", sync.pool_address)) else {
+            continue;
+        };
+        let reserve0 = to_decimal(&sync.reserve0, token_decimals(&tokens, &pool.token0));
+        let reserve1 = to_decimal(&sync.reserve1, token_decimals(&tokens, &pool.token1));
+        let tvl = tvl_usd(
+            &reserve0,
+            pricing::usd_price(&token_prices, sync.ordinal, &pool.token0),
+            &reserve1,
+            pricing::usd_price(&token_prices, sync.ordinal, &pool.token1),
+        );
+        if let Some(tvl) = tvl {
+            store.set(sync.ordinal, format!("pool:{}", pool.address), &tvl);
+        }
+    }
+}
+
+/// Store handler for protocol-wide TVL in USD
+///
+/// Key formats:
+/// - `total` - Sum of the TVL of every priced pool
+#[substreams::handlers::store]
+pub fn store_protocol_tvl(pool_tvl: Deltas<DeltaBigDecimal>, store: StoreAddBigDecimal) {
+    for delta in &pool_tvl.deltas {
+        let change = delta.new_value.clone() - delta.old_value.clone();
+        store.add(delta.ordinal, "total", &change);
+    }
+}
+
+/// Emit the reserves and TVL of every pool synced in the block, along with
+/// the protocol TVL
+#[substreams::handlers::map]
+pub fn map_pool_tvl(
+    clock: Clock,
+    syncs: SyncEvents,
+    pools: StoreGetProto<Pool>,
+    tokens: StoreGetProto<Token>,
+    reserves: StoreGetBigInt,
+    pool_tvl: StoreGetBigDecimal,
+    protocol_tvl: StoreGetBigDecimal,
+) -> Result<PoolTvlSnapshots, substreams::errors::Error> {
+    let changed: BTreeSet<&str> = syncs
+        .events
+        .iter()
+        .map(|sync| sync.pool_address.as_str())
+        .collect();
+
+    let mut snapshots = Vec::new();
+    for address in changed {
+        let Some(pool) = pools.get_last(format!("pool:{}", address)) else {
+            continue;
+        };
+        let get = |key: String| reserves.get_last(key).unwrap_or_else(BigInt::zero);
+        let reserve0 = get(format!("pool:{}:reserve0", address));
+        let reserve1 = get(format!("pool:{}:reserve1", address));
+        let decimals0 = token_decimals(&tokens, &pool.token0);
+        let decimals1 = token_decimals(&tokens, &pool.token1);
+
+        snapshots.push(PoolTvl {
+            pool_address: pool.address,
+            reserve0_decimal: to_decimal(&reserve0.to_string(), decimals0).to_string(),
+            reserve1_decimal: to_decimal(&reserve1.to_string(), decimals1).to_string(),
+            reserve0: reserve0.to_string(),
+            reserve1: reserve1.to_string(),
+            token0: pool.token0,
+            token1: pool.token1,
+            tvl_usd: pool_tvl
+                .get_last(format!("pool:{}", address))
+                .map(|tvl| tvl.to_string())
+                .unwrap_or_default(),
+        });
+    }
+
+    Ok(PoolTvlSnapshots {
+        block_number: clock.number,
+        timestamp: clock
+            .timestamp
+            .map(|t| t.seconds as u64)
+            .unwrap_or_default(),
+        pools: snapshots,
+        protocol_tvl_usd: protocol_tvl
+            .get_last("total")
+            .unwrap_or_else(BigDecimal::zero)
+            .to_string(),
+    })
+}
+
+/// USD value of a pool's reserves, doubling the priced side when the other
+/// token has no price
+fn tvl_usd(
+    reserve0: &BigDecimal,
+    price0: Option<BigDecimal>,
+    reserve1: &BigDecimal,
+    price1: Option<BigDecimal>,
+) -> Option<BigDecimal> {
+    let value = match (price0, price1) {
+        (Some(price0), Some(price1)) => reserve0.clone() * price0 + reserve1.clone() * price1,
+        (Some(price0), None) => reserve0.clone() * price0 * BigDecimal::from(2),
+        (None, Some(price1)) => reserve1.clone() * price1 * BigDecimal::from(2),
+        (None, None) => return None,
+    };
+    Some(prices::round(value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_tvl_usd() {
+        let weth = BigDecimal::from_str("10").unwrap();
+        let usdc = BigDecimal::from_str("25000").unwrap();
+        let price = BigDecimal::from_str("2500").unwrap();
+
+        let tvl = tvl_usd(&weth, Some(price.clone()), &usdc, Some(BigDecimal::one()));
+        assert_eq!(tvl, Some(BigDecimal::from(50000)));
+
+        // Only WETH priced: twice its side
+        let tvl = tvl_usd(&weth, Some(price), &usdc, None);
+        assert_eq!(tvl, Some(BigDecimal::from(50000)));
+
+        assert_eq!(tvl_usd(&weth, None, &usdc, None), None);
+    }
+}
@@ -0,0 +1,174 @@
+//! Voter votes and gauge lifecycle
+//!
+//! veAERO locks vote for pools every epoch, and the Voter reports each pool's
+//! total weight after every `Voted` and `Abstained`, so the latest value in
+//! an epoch is that pool's vote weight for the epoch.
+
+use crate::abi::voter::events::{
+    Abstained, DistributeReward, GaugeCreated, GaugeKilled, GaugeRevived, Voted, WhitelistToken,
+};
+use crate::calendar::epoch_number;
+use crate::math::parse_bigint;
+use crate::pb::aerodrome::{AerodromeGovernance, GovernanceEvents};
+use hex_literal::hex;
+use substreams::store::{StoreNew, StoreSet, StoreSetBigInt};
+use substreams::Hex;
+use substreams_ethereum::pb::eth::v2 as eth;
+use substreams_ethereum::Event;
+
+/// Aerodrome Voter on Base
+const VOTER: [u8; 20] = hex!("16613524e02ad97edfef371bc883f2f5d6c480a5");
+
+/// Extract votes and gauge lifecycle events from the Voter
+#[substreams::handlers::map]
+pub fn map_votes(blk: eth::Block) -> Result<GovernanceEvents, substreams::errors::Error> {
+    let timestamp = blk
+        .header
+        .as_ref()
+        .and_then(|h| h.timestamp.as_ref())
+        .map(|t| t.seconds)
+        .unwrap_or(0) as u64;
+
+    let mut events = Vec::new();
+
+    for receipt in blk.receipts() {
+        let tx_hash = Hex(&receipt.transaction.hash).to_string();
+
+        for log in &receipt.receipt.logs {
+            if log.address != VOTER {
+                continue;
+            }
+
+            let base = AerodromeGovernance {
+                block_number: blk.number,
+                transaction_hash: tx_hash.clone(),
+                log_index: log.index as u64,
+                timestamp,
+                epoch: epoch_number(timestamp),
+                ..Default::default()
+            };
+
+            if let Some(event) = decode_event(log, base) {
+                events.push(event);
+            }
+        }
+    }
+
+    Ok(GovernanceEvents { events })
+}
+
+/// Decode a Voter log into `event`, `None` for logs that are not indexed
+fn decode_event(log: &eth::Log, mut event: AerodromeGovernance) -> Option<AerodromeGovernance> {
+    if let Some(voted) = Voted::match_and_decode(log) {
+        event.action = "vote".to_string();
+        event.voter = Hex(&voted.voter).to_string();
+        event.pool_address = Hex(&voted.pool).to_string();
+        event.token_id = voted.token_id.to_string();
+        event.amount = voted.weight.to_string();
+        event.pool_weight = voted.total_weight.to_string();
+    } else if let Some(abstained) = Abstained::match_and_decode(log) {
+        event.action = "abstain".to_string();
+        event.voter = Hex(&abstained.voter).to_string();
+        event.pool_address = Hex(&abstained.pool).to_string();
+        event.token_id = abstained.token_id.to_string();
+        event.amount = abstained.weight.to_string();
+        event.pool_weight = abstained.total_weight.to_string();
+    } else if let Some(created) = GaugeCreated::match_and_decode(log) {
+        event.action = "gauge_created".to_string();
+        event.voter = Hex(&created.creator).to_string();
+        event.pool_address = Hex(&created.pool).to_string();
+        event.gauge = Hex(&created.gauge).to_string();
+        event.bribe_voting_reward = Hex(&created.bribe_voting_reward).to_string();
+        event.fees_voting_reward = Hex(&created.fee_voting_reward).to_string();
+    } else if let Some(killed) = GaugeKilled::match_and_decode(log) {
+        event.action = "gauge_killed".to_string();
+        event.gauge = Hex(&killed.gauge).to_string();
+    } else if let Some(revived) = GaugeRevived::match_and_decode(log) {
+        event.action = "gauge_revived".to_string();
+        event.gauge = Hex(&revived.gauge).to_string();
+    } else if let Some(whitelist) = WhitelistToken::match_and_decode(log) {
+        event.action = "whitelist_token".to_string();
+        event.voter = Hex(&whitelist.whitelister).to_string();
+        event.token = Hex(&whitelist.token).to_string();
+        event.whitelisted = whitelist.bool;
+    } else if let Some(distribute) = DistributeReward::match_and_decode(log) {
+        event.action = "distribute_reward".to_string();
+        event.voter = Hex(&distribute.sender).to_string();
+        event.gauge = Hex(&distribute.gauge).to_string();
+        event.amount = distribute.amount.to_string();
+    } else {
+        return None;
+    }
+
+    Some(event)
+}
+
+/// Store handler for pool vote weights
+///
+/// Weights carry over between epochs until locks vote again, so an epoch
+/// only has a key for pools whose weight changed during it.
+///
+/// Key formats:
+/// - `epoch:{n}:pool:{address}` - Pool vote weight after the epoch's last vote
+#[substreams::handlers::store]
+pub fn store_votes(events: GovernanceEvents, store: StoreSetBigInt) {
+    for event in &events.events {
+        if event.action != "vote" && event.action != "abstain" {
+            continue;
+        }
+        store.set(
+            0,
+            format!("epoch:{}:pool:{}", event.epoch, event.pool_address),
+            &parse_bigint(&event.pool_weight),
+        );
+    }
+}
+
+/// Split an `epoch:{n}:pool:{address}` key into its epoch and pool
+pub(crate) fn parse_epoch_pool_key(key: &str) -> Option<(u64, &str)> {
+    let mut parts = key.split(':');
+    match (parts.next(), parts.next(), parts.next(), parts.next()) {
+        (Some("epoch"), Some(epoch), Some("pool"), Some(pool)) => Some((epoch.parse().ok()?, pool)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_distribute_reward() {
+        let minter = hex!("eb018363f0a9af8f91f06fee6613a751b2a33fe5");
+        let gauge = hex!("519bbd1dd8c6a94c46080e24f316c14ee758c025");
+        let topic = |address: [u8; 20]| [vec![0u8; 12], address.to_vec()].concat();
+        let mut amount = vec![0u8; 16];
+        amount.extend_from_slice(&1_500_000_000_000_000_000_000u128.to_be_bytes());
+
+        let log = eth::Log {
+            address: VOTER.to_vec(),
+            topics: vec![
+                hex!("4fa9693cae526341d334e2862ca2413b2e503f1266255f9e0869fb36e6d89b17").to_vec(),
+                topic(minter),
+                topic(gauge),
+            ],
+            data: amount,
+            ..Default::default()
+        };
+
+        let event = decode_event(&log, AerodromeGovernance::default()).expect("should decode");
+        assert_eq!(event.action, "distribute_reward");
+        assert_eq!(event.voter, Hex(minter).to_string());
+        assert_eq!(event.gauge, Hex(gauge).to_string());
+        assert_eq!(event.amount, "1500000000000000000000");
+    }
+
+    #[test]
+    fn test_parse_epoch_pool_key() {
+        assert_eq!(
+            parse_epoch_pool_key("epoch:2818:pool:abc"),
+            Some((2818, "abc"))
+        );
+        assert_eq!(parse_epoch_pool_key("pool:abc:weight"), None);
+    }
+}